@@ -0,0 +1,195 @@
+//! Small pieces shared by the `aoc` and `aoc-downloader` commands, so that
+//! they agree on them.
+
+use std::{
+    fmt::Display,
+    ops::RangeInclusive,
+    ops::Sub,
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+
+pub type Year = i32;
+pub type Day = u32;
+
+/// The year of the first Advent of Code.
+pub const FIRST_YEAR: Year = 2015;
+
+/// The first year with the shorter, 12 day event.
+const FIRST_SHORT_YEAR: Year = 2025;
+
+pub fn days_in_event(year: Year) -> Day {
+    if year >= FIRST_SHORT_YEAR { 12 } else { 25 }
+}
+
+/// Whether there is (or will be) a puzzle on this day.
+pub fn is_puzzle_day(year: Year, day: Day) -> bool {
+    year >= FIRST_YEAR && (1..=days_in_event(year)).contains(&day)
+}
+
+/// Where the downloader moves responses that weren't puzzle inputs, inside an
+/// inputs directory.
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+/// Where a profile's inputs are kept, under `dir` when there's a profile.
+pub fn profile_dir(dir: &Path, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(profile) => dir.join(profile),
+        None => dir.to_path_buf(),
+    }
+}
+
+/// Marks an example's answers as needing checking by hand, such as ones
+/// extracted from a puzzle description by the downloader, followed by why.
+pub const REVIEW_PREFIX: &str = "# review:";
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Where the solutions crate keeps the answers to a profile's inputs, next to
+/// the inputs directory.
+pub fn answers_path(inputs: &Path, profile: Option<&str>) -> PathBuf {
+    let name = match profile {
+        Some(profile) => format!("answers-{profile}.txt"),
+        None => ANSWERS_FILE.to_string(),
+    };
+    inputs.parent().unwrap_or(Path::new("")).join(name)
+}
+
+/// Check a profile name, which has to be a single plain path component that
+/// doesn't clash with anything else in an inputs directory.
+pub fn parse_profile(name: &str) -> Result<String, String> {
+    let mut components = Path::new(name).components();
+    let plain = matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(component)), None) if component == name
+    );
+    if !plain {
+        return Err(format!(
+            "'{name}' is not a plain name, it can't be '.' or '..' or contain a path separator"
+        ));
+    }
+    let year_dir = name
+        .strip_prefix('y')
+        .is_some_and(|year| !year.is_empty() && year.bytes().all(|b| b.is_ascii_digit()));
+    if name == QUARANTINE_DIR || year_dir {
+        return Err(format!("'{name}' is used by the inputs directory itself"));
+    }
+    Ok(name.to_string())
+}
+
+/// Parse a value (`5`) or a range (`1..10`, `1..=10`, `..5` or `20..`), where
+/// open ends are filled in with `min` and `max`.
+pub fn parse_range<T>(value: &str, min: T, max: T) -> Result<RangeInclusive<T>, String>
+where
+    T: FromStr + Copy + PartialOrd + Sub<Output = T> + From<u8>,
+    T::Err: Display,
+{
+    let parse = |part: &str, default: T| {
+        if part.is_empty() {
+            Ok(default)
+        } else {
+            part.parse::<T>()
+                .map_err(|error| format!("invalid value '{part}': {error}"))
+        }
+    };
+
+    let range = if let Some((start, end)) = value.split_once("..=") {
+        parse(start, min)?..=parse(end, max)?
+    } else if let Some((start, end)) = value.split_once("..") {
+        let start = parse(start, min)?;
+        if end.is_empty() {
+            start..=max
+        } else {
+            let end = parse(end, max)?;
+            if end <= min {
+                return Err(format!("'{value}' is empty"));
+            }
+            start..=(end - T::from(1))
+        }
+    } else {
+        let value = parse(value, min)?;
+        value..=value
+    };
+
+    if range.is_empty() {
+        return Err(format!("'{value}' is empty"));
+    }
+    Ok(range)
+}
+
+/// Parse a day or a range of days, see [`parse_range`].
+pub fn parse_days<T>(value: &str) -> Result<RangeInclusive<T>, String>
+where
+    T: FromStr + Copy + PartialOrd + Sub<Output = T> + From<u8>,
+    T::Err: Display,
+{
+    parse_range(value, T::from(1), T::from(25))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_puzzle_day() {
+        assert!(is_puzzle_day(2015, 1));
+        assert!(is_puzzle_day(2024, 25));
+        assert!(is_puzzle_day(2025, 12));
+        assert!(!is_puzzle_day(2025, 13));
+        assert!(!is_puzzle_day(2024, 0));
+        assert!(!is_puzzle_day(2014, 1));
+    }
+
+    #[test]
+    fn test_answers_path() {
+        let inputs = Path::new("inputs");
+        assert_eq!(
+            answers_path(inputs, Some("work")),
+            Path::new("answers-work.txt")
+        );
+        assert_eq!(answers_path(inputs, None), Path::new("answers.txt"));
+        assert_eq!(
+            answers_path(Path::new("data/inputs"), None),
+            Path::new("data/answers.txt")
+        );
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(parse_profile("work"), Ok("work".to_string()));
+        assert_eq!(parse_profile("year-2"), Ok("year-2".to_string()));
+        for name in [
+            "",
+            ".",
+            "..",
+            "../x",
+            "/x",
+            "a/b",
+            "a/",
+            "./a",
+            "quarantine",
+            "y2024",
+        ] {
+            assert!(parse_profile(name).is_err(), "{name:?}");
+        }
+        assert_eq!(
+            profile_dir(Path::new("inputs"), Some("work")),
+            Path::new("inputs/work")
+        );
+        assert_eq!(profile_dir(Path::new("inputs"), None), Path::new("inputs"));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_days("5"), Ok(5..=5));
+        assert_eq!(parse_days("1..=10"), Ok(1..=10));
+        assert_eq!(parse_days("1..10"), Ok(1..=9));
+        assert_eq!(parse_days("..5"), Ok(1..=4));
+        assert_eq!(parse_days("20.."), Ok(20usize..=25));
+        assert!(parse_days::<u32>("10..1").is_err());
+        assert!(parse_days::<u32>("..1").is_err());
+        assert!(parse_days::<u32>("x").is_err());
+        assert_eq!(parse_range("2023..", 2015, i32::MAX), Ok(2023..=i32::MAX));
+        assert_eq!(parse_range("..2017", 2015, i32::MAX), Ok(2015..=2016));
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
+
+pub use aoc_common::{FIRST_YEAR, days_in_event, is_puzzle_day};
+
+use crate::{Day, Year};
+
+/// Puzzles unlock at midnight in UTC-5.
+pub fn release_timezone() -> FixedOffset {
+    FixedOffset::west_opt(60 * 60 * 5).unwrap()
+}
+
+/// When a puzzle unlocks, at midnight UTC-5 on that day of December, or `None` if there is no
+/// such day.
+pub fn unlock_time(year: Year, day: Day) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::from_ymd_opt(year, 12, day)?;
+    let unlock = release_timezone().from_local_datetime(&date.and_hms_opt(0, 0, 0)?);
+    Some(unlock.single()?.to_utc())
+}
+
+pub fn is_unlocked(year: Year, day: Day, now: DateTime<Utc>) -> bool {
+    is_puzzle_day(year, day) && unlock_time(year, day).is_some_and(|unlock| unlock <= now)
+}
+
+/// Every puzzle that has unlocked by `now`, in order.
+pub fn unlocked_days(now: DateTime<Utc>) -> Vec<(Year, Day)> {
+    let last_year = now.with_timezone(&release_timezone()).year();
+    (FIRST_YEAR..=last_year)
+        .flat_map(|year| (1..=days_in_event(year)).map(move |day| (year, day)))
+        .filter(|&(year, day)| is_unlocked(year, day, now))
+        .collect()
+}
+
+/// The year of the latest event with a puzzle out by `now`.
+pub fn latest_event(now: DateTime<Utc>) -> Year {
+    unlocked_days(now)
+        .last()
+        .map_or(FIRST_YEAR, |&(year, _)| year)
+}
+
+/// The next puzzle to unlock after `now`, and when it unlocks.
+pub fn next_unlock(now: DateTime<Utc>) -> (Year, Day, DateTime<Utc>) {
+    let year = now.with_timezone(&release_timezone()).year();
+    (1..=days_in_event(year))
+        .map(|day| (year, day))
+        .chain([(year + 1, 1)])
+        .filter_map(|(year, day)| Some((year, day, unlock_time(year, day)?)))
+        .find(|(_, _, unlock)| *unlock > now)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().to_utc()
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(unlock_time(2024, 1), Some(utc("2024-12-01T05:00:00Z")));
+        assert_eq!(unlock_time(2025, 12), Some(utc("2025-12-12T05:00:00Z")));
+        assert_eq!(unlock_time(2024, 0), None);
+        assert_eq!(unlock_time(2024, 32), None);
+        assert_eq!(unlock_time(Year::MAX, 1), None);
+        assert!(!is_unlocked(2024, 1, utc("2024-12-01T04:59:59Z")));
+        assert!(is_unlocked(2024, 1, utc("2024-12-01T05:00:00Z")));
+        assert!(!is_unlocked(2024, 26, utc("2030-01-01T00:00:00Z")));
+        assert!(!is_unlocked(2025, 13, utc("2030-01-01T00:00:00Z")));
+        assert!(!is_unlocked(2014, 1, utc("2030-01-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_unlocked_days() {
+        // Outside December, nothing from the current year is out yet.
+        let days = unlocked_days(utc("2026-10-18T12:00:00Z"));
+        assert_eq!(days.first(), Some(&(2015, 1)));
+        assert_eq!(days.last(), Some(&(2025, 12)));
+        assert_eq!(days.len(), 10 * 25 + 12);
+        assert!(days.contains(&(2024, 25)));
+
+        // Midnight UTC-5 on the 1st of December is still the 30th of November in UTC-5
+        // until 05:00 UTC.
+        let days = unlocked_days(utc("2025-12-01T04:59:59Z"));
+        assert_eq!(days.last(), Some(&(2024, 25)));
+        let days = unlocked_days(utc("2025-12-01T05:00:00Z"));
+        assert_eq!(days.last(), Some(&(2025, 1)));
+
+        // Just after New Year in UTC, it's still the last year in UTC-5.
+        let days = unlocked_days(utc("2026-01-01T02:00:00Z"));
+        assert_eq!(days.last(), Some(&(2025, 12)));
+        assert_eq!(latest_event(utc("2026-10-18T12:00:00Z")), 2025);
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        assert_eq!(
+            next_unlock(utc("2026-10-18T12:00:00Z")),
+            (2026, 1, utc("2026-12-01T05:00:00Z"))
+        );
+        assert_eq!(
+            next_unlock(utc("2025-12-05T05:00:00Z")),
+            (2025, 6, utc("2025-12-06T05:00:00Z"))
+        );
+        assert_eq!(
+            next_unlock(utc("2025-12-12T05:00:01Z")),
+            (2026, 1, utc("2026-12-01T05:00:00Z"))
+        );
+        assert_eq!(
+            next_unlock(utc("2024-12-25T04:00:00Z")),
+            (2024, 25, utc("2024-12-25T05:00:00Z"))
+        );
+    }
+}
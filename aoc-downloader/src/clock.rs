@@ -0,0 +1,52 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+
+/// Where the time comes from, so that waiting can be tested without waiting.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Sleep until `time`, returning straight away if it has already passed.
+    fn sleep_until(&self, time: DateTime<Utc>) -> impl Future<Output = ()>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep_until(&self, time: DateTime<Utc>) {
+        if let Ok(duration) = (time - Utc::now()).to_std() {
+            tokio::time::sleep(duration).await;
+        }
+    }
+}
+
+/// A clock that jumps straight to the end of each sleep.
+#[cfg(test)]
+pub struct FakeClock {
+    pub now: std::sync::Mutex<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> FakeClock {
+        FakeClock {
+            now: std::sync::Mutex::new(now),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    async fn sleep_until(&self, time: DateTime<Utc>) {
+        let mut now = self.now.lock().unwrap();
+        *now = time.max(*now);
+    }
+}
@@ -0,0 +1,271 @@
+use url::Url;
+
+use crate::{Day, Error, Year, http::Http};
+
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// The HTML inside each `<article class="day-desc">` of a puzzle page, one per
+/// unlocked part.
+pub fn articles(page: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = page;
+    while let Some((_, after)) = rest.split_once("<article class=\"day-desc\">") {
+        let Some((article, after)) = after.split_once("</article>") else {
+            break;
+        };
+        articles.push(article);
+        rest = after;
+    }
+    articles
+}
+
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| &rest[1..end]);
+        let c = match entity {
+            Some("lt") => Some('<'),
+            Some("gt") => Some('>'),
+            Some("amp") => Some('&'),
+            Some("quot") => Some('"'),
+            Some("apos") => Some('\''),
+            Some(entity) => entity
+                .strip_prefix('#')
+                .and_then(|code| code.parse().ok())
+                .and_then(char::from_u32),
+            None => None,
+        };
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The value of an attribute in an opening tag.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let (_, rest) = tag.split_once(&format!(" {name}=\""))?;
+    Some(rest.split_once('"')?.0)
+}
+
+/// End the current block, leaving a blank line before the next one.
+fn end_block(markdown: &mut String) {
+    markdown.truncate(markdown.trim_end().len());
+    if !markdown.is_empty() {
+        markdown.push_str("\n\n");
+    }
+}
+
+/// Convert the HTML of a puzzle's articles to Markdown. Only the handful of
+/// tags that puzzles use are understood, and the rest are dropped.
+fn to_markdown(html: &str, page_url: &Url) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    // Where the current inline code starts, and whether it's emphasised.
+    let mut code: Option<(usize, bool)> = None;
+    let mut links = vec![];
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let (text, tag) = match rest.find('<') {
+            Some(start) => {
+                let end = rest[start..]
+                    .find('>')
+                    .map_or(rest.len(), |end| start + end + 1);
+                let split = (&rest[..start], &rest[start..end]);
+                rest = &rest[end..];
+                split
+            }
+            None => (std::mem::take(&mut rest), ""),
+        };
+
+        let text = decode_entities(text);
+        if in_pre {
+            markdown.push_str(&text);
+        } else if code.is_some() {
+            markdown.push_str(&text.replace('\n', " "));
+        } else {
+            let mut text = text.replace('\n', " ");
+            if markdown.is_empty() || markdown.ends_with('\n') {
+                text = text.trim_start().to_string();
+            }
+            for c in text.chars() {
+                if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+                    markdown.push('\\');
+                }
+                markdown.push(c);
+            }
+        }
+
+        if tag.is_empty() {
+            continue;
+        }
+        let closing = tag.starts_with("</");
+        let name = tag
+            .trim_start_matches(['<', '/'])
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match (name.as_str(), closing) {
+            ("pre", false) => {
+                end_block(&mut markdown);
+                markdown.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+                in_pre = false;
+            }
+            _ if in_pre => {}
+            ("h2", false) => {
+                end_block(&mut markdown);
+                markdown.push_str("## ");
+            }
+            ("h2" | "p" | "ul", true) => end_block(&mut markdown),
+            ("p" | "ul", false) => end_block(&mut markdown),
+            ("li", false) => {
+                markdown.truncate(markdown.trim_end_matches(' ').len());
+                if !markdown.is_empty() && !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("- ");
+            }
+            ("code", false) => {
+                code = Some((markdown.len(), false));
+                markdown.push('`');
+            }
+            ("code", true) => {
+                markdown.push('`');
+                // Markdown has no emphasis inside code, so emphasise all of it.
+                if let Some((start, true)) = code {
+                    markdown.insert(start, '*');
+                    markdown.push('*');
+                }
+                code = None;
+            }
+            ("em", _) => match &mut code {
+                Some((_, emphasised)) => *emphasised = true,
+                None => markdown.push('*'),
+            },
+            ("a", false) => {
+                let href = attribute(tag, "href").map(decode_entities);
+                let url = href
+                    .and_then(|href| page_url.join(&href).ok())
+                    .map_or_else(String::new, String::from);
+                links.push(url);
+                markdown.push('[');
+            }
+            ("a", true) => {
+                let url = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({url})"));
+            }
+            _ => {}
+        }
+    }
+
+    markdown.truncate(markdown.trim_end().len());
+    markdown.push('\n');
+    markdown
+}
+
+/// The puzzle description from a puzzle page, as Markdown, if it has one.
+pub fn page_to_markdown(page: &str, page_url: &Url) -> Option<String> {
+    let articles = articles(page);
+    if articles.is_empty() {
+        return None;
+    }
+    Some(to_markdown(&articles.join("\n"), page_url))
+}
+
+/// Whether a saved description includes part two, which is only shown once
+/// part one is solved.
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.contains(PART_TWO_HEADING)
+}
+
+/// Download a puzzle's page, returning its URL with it for resolving links.
+pub async fn download_page(
+    http: &Http,
+    base: &Url,
+    year: Year,
+    day: Day,
+) -> Result<(Url, String), Error> {
+    let url = crate::build_page_url(base, year, day)?;
+    let response = http.get(url.clone()).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {status}").into());
+    }
+    Ok((url, response.text().await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_to_markdown() {
+        let page = r#"<!DOCTYPE html>
+<html lang="en-us"><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is <a href="/2024/day/1/input" target="_blank">missing</a> &amp; the lists are <span title="Easter egg">weird</span>:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul>
+<li>The first pair is <code>3</code> and <code>4</code>.</li>
+<li>The total is <code><em>11</em></code>, using <code>a*b</code>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>What is their <em>similarity score?</em></p>
+</article>
+</main></body></html>"#;
+        let url = Url::parse("https://adventofcode.com/2024/day/1").unwrap();
+        let markdown = page_to_markdown(page, &url).unwrap();
+        assert_eq!(
+            markdown,
+            "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is [missing](https://adventofcode.com/2024/day/1/input) & the lists are weird:
+
+```
+3   4
+4   3
+```
+
+- The first pair is `3` and `4`.
+- The total is *`11`*, using `a*b`.
+
+## --- Part Two ---
+
+What is their *similarity score?*
+"
+        );
+        assert!(has_part_two(&markdown));
+
+        let part_one = page.split("<p>Your puzzle").next().unwrap();
+        assert!(!has_part_two(&page_to_markdown(part_one, &url).unwrap()));
+        assert_eq!(page_to_markdown("<html></html>", &url), None);
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("&lt;a&gt; &amp; &#39;b&#39;"), "<a> & 'b'");
+        assert_eq!(decode_entities("a & b; &unknown;"), "a & b; &unknown;");
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use aoc_vault::Key;
+
+use crate::{Error, input_path, saved_days};
+
+/// Counts of the files encrypted into the vault.
+#[derive(Debug, Default, PartialEq)]
+pub struct Encrypted {
+    pub written: usize,
+    pub unchanged: usize,
+}
+
+impl Encrypted {
+    fn add(&mut self, path: &Path, key: &Key, data: &[u8]) -> Result<(), Error> {
+        if aoc_vault::write(path, key, data)? {
+            eprintln!("Encrypted {}", path.display());
+            self.written += 1;
+        } else {
+            self.unchanged += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The key from a key file, or else from the environment.
+pub fn load_key(key_file: Option<&Path>) -> Result<Key, Error> {
+    let key = match key_file {
+        Some(path) => Some(Key::from_file(path)?),
+        None => Key::from_env()?,
+    };
+    key.ok_or_else(|| {
+        format!(
+            "a key is needed, use --key-file or set {}",
+            aoc_vault::PASSPHRASE_VAR
+        )
+        .into()
+    })
+}
+
+/// Encrypt every saved input into the vault, along with the answers file if
+/// there is one.
+pub fn encrypt_inputs(
+    output: &Path,
+    answers: &Path,
+    vault: &Path,
+    key: &Key,
+) -> Result<Encrypted, Error> {
+    let mut encrypted = Encrypted::default();
+    for (year, day) in saved_days(output)? {
+        let data = std::fs::read(input_path(output, year, day))?;
+        let path = aoc_vault::input_path(vault, year.try_into()?, day.try_into()?);
+        encrypted.add(&path, key, &data)?;
+    }
+
+    if let Ok(data) = std::fs::read(answers) {
+        encrypted.add(&aoc_vault::answers_path(vault), key, &data)?;
+    }
+    Ok(encrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_inputs() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let output = dir.join("inputs");
+        let vault = dir.join("vault");
+        std::fs::create_dir_all(output.join("y2024")).unwrap();
+        std::fs::write(input_path(&output, 2024, 1), "1 2\n").unwrap();
+        std::fs::write(input_path(&output, 2024, 2), "3 4\n").unwrap();
+        std::fs::write(dir.join("answers.txt"), "2024 1 1 3\n").unwrap();
+        let answers = dir.join("answers.txt");
+        let key = Key::Identity(age::x25519::Identity::generate());
+
+        let encrypted = encrypt_inputs(&output, &answers, &vault, &key).unwrap();
+        assert_eq!(
+            encrypted,
+            Encrypted {
+                written: 3,
+                unchanged: 0
+            }
+        );
+        let path = aoc_vault::input_path(&vault, 2024, 2);
+        assert_eq!(aoc_vault::read(&path, Some(&key)).unwrap(), b"3 4\n");
+
+        std::fs::write(input_path(&output, 2024, 2), "5 6\n").unwrap();
+        let encrypted = encrypt_inputs(&output, &answers, &vault, &key).unwrap();
+        assert_eq!(
+            encrypted,
+            Encrypted {
+                written: 1,
+                unchanged: 2
+            }
+        );
+    }
+}
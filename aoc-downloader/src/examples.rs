@@ -0,0 +1,264 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::REVIEW_PREFIX;
+
+use crate::{Day, Error, Year, describe};
+
+/// Extracted examples are named `extracted<n>`, to keep them apart from ones
+/// written by hand.
+const PREFIX: &str = "extracted";
+
+/// An example input found in a puzzle description, with the answers that seem
+/// to go with it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Candidate {
+    pub input: String,
+    pub answers: BTreeMap<u8, String>,
+    /// Why the heuristics might have got it wrong, if they're unsure.
+    pub review: Vec<String>,
+}
+
+/// The HTML between each `open` and `close` in `html`, with where it starts.
+fn find_all<'a>(html: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut offset = 0;
+    while let Some(start) = html[offset..].find(open) {
+        let start = offset + start + open.len();
+        let Some(end) = html[start..].find(close) else {
+            break;
+        };
+        found.push((start, &html[start..start + end]));
+        offset = start + end + close.len();
+    }
+    found
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    describe::decode_entities(&text)
+}
+
+/// Find the examples in a puzzle page.
+///
+/// Each part's example is taken to be the first code block of its article,
+/// and its answer the last emphasised code after that block. Part two
+/// usually reuses part one's example, so its answer goes with that example
+/// when its article has no code block of its own.
+pub fn extract(page: &str) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = vec![];
+    for (part, article) in (1..=2).zip(describe::articles(page)) {
+        let blocks = find_all(article, "<pre><code>", "</code></pre>");
+        let candidate = match blocks.first() {
+            Some((_, block)) => {
+                let input = strip_tags(block);
+                match candidates
+                    .iter()
+                    .position(|candidate| candidate.input == input)
+                {
+                    Some(index) => &mut candidates[index],
+                    None => {
+                        candidates.push(Candidate {
+                            input,
+                            ..Default::default()
+                        });
+                        candidates.last_mut().unwrap()
+                    }
+                }
+            }
+            None => match candidates.first_mut() {
+                Some(candidate) => candidate,
+                None => continue,
+            },
+        };
+        if blocks.len() > 1 {
+            candidate.review.push(format!(
+                "part {part} has {} code blocks, and the first was taken as the example",
+                blocks.len()
+            ));
+        }
+
+        let start = blocks.first().map_or(0, |(start, _)| *start);
+        let answers: Vec<String> = find_all(article, "<code><em>", "</em></code>")
+            .into_iter()
+            .filter(|(position, _)| *position > start)
+            .map(|(_, answer)| strip_tags(answer))
+            .collect();
+        let Some(answer) = answers.last() else {
+            candidate
+                .review
+                .push(format!("no answer was found for part {part}"));
+            continue;
+        };
+        if answers.iter().collect::<BTreeSet<_>>().len() > 1 {
+            candidate.review.push(format!(
+                "part {part} emphasises {} values, and the last was taken as the answer",
+                answers.len()
+            ));
+        }
+        if answer.parse::<i64>().is_err() {
+            candidate
+                .review
+                .push(format!("the answer to part {part} isn't a number"));
+        }
+        candidate.answers.insert(part, answer.clone());
+    }
+    candidates
+}
+
+fn day_dir(dir: &Path, year: Year, day: Day) -> PathBuf {
+    dir.join(format!("y{year}")).join(format!("day{day:02}"))
+}
+
+/// The answers files of the examples extracted for a day.
+fn extracted_answers(dir: &Path, year: Year, day: Day) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(day_dir(dir, year, day)) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "answers")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem.starts_with(PREFIX))
+        })
+        .collect()
+}
+
+/// Whether a day's examples haven't been extracted with a part two answer yet.
+pub fn needs_examples(dir: &Path, year: Year, day: Day) -> bool {
+    !extracted_answers(dir, year, day).iter().any(|path| {
+        std::fs::read_to_string(path)
+            .is_ok_and(|answers| answers.lines().any(|line| line.starts_with("2 ")))
+    })
+}
+
+/// Save the examples as `<name>.txt` with their answers in `<name>.answers`,
+/// replacing any that were extracted before. Returns the directory they were
+/// saved in.
+pub fn save(dir: &Path, year: Year, day: Day, candidates: &[Candidate]) -> Result<PathBuf, Error> {
+    let day_dir = day_dir(dir, year, day);
+    for answers_path in extracted_answers(dir, year, day) {
+        // The input may have been deleted by hand, which is fine.
+        for path in [answers_path.with_extension("txt"), answers_path] {
+            match std::fs::remove_file(&path) {
+                Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(error.into());
+                }
+                _ => {}
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return Ok(day_dir);
+    }
+
+    std::fs::create_dir_all(&day_dir)?;
+    for (i, candidate) in candidates.iter().enumerate() {
+        let name = format!("{PREFIX}{}", i + 1);
+        let mut answers = String::from("# Extracted from the puzzle description\n");
+        for reason in &candidate.review {
+            writeln!(answers, "{REVIEW_PREFIX} {reason}")?;
+        }
+        for (part, answer) in &candidate.answers {
+            writeln!(answers, "{part} {answer}")?;
+        }
+        std::fs::write(day_dir.join(format!("{name}.txt")), &candidate.input)?;
+        std::fs::write(day_dir.join(format!("{name}.answers")), answers)?;
+    }
+    Ok(day_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(html: &str) -> String {
+        format!("<article class=\"day-desc\">{html}</article>")
+    }
+
+    #[test]
+    fn test_extract() {
+        let part_one = article(
+            "<p>For example:</p><pre><code>3   4\n4   3\n</code></pre>\
+             <p>The distances are <code>2</code> and <code>1</code>, for a total of <code><em>3</em></code>.</p>\
+             <p><em>What is the total distance?</em></p>",
+        );
+        let part_two = article(
+            "<h2 id=\"part2\">--- Part Two ---</h2>\
+             <p>In the example above, the similarity score is <code><em>12</em></code>.</p>",
+        );
+        let page = format!(
+            "<main>{part_one}<p>Your puzzle answer was <code>1</code>.</p>{part_two}</main>"
+        );
+        assert_eq!(
+            extract(&page),
+            vec![Candidate {
+                input: "3   4\n4   3\n".to_string(),
+                answers: BTreeMap::from([(1, "3".to_string()), (2, "12".to_string())]),
+                review: vec![],
+            }]
+        );
+
+        // Part two has its own example, and too many candidates for the answer.
+        let part_two = article(
+            "<pre><code>a &lt; b\n</code></pre><pre><code>x\n</code></pre>\
+             <p>Now <code><em>4</em></code> and then <code><em>ab</em></code>.</p>",
+        );
+        let candidates = extract(&format!("{part_one}{part_two}"));
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[1].input, "a < b\n");
+        assert_eq!(
+            candidates[1].answers,
+            BTreeMap::from([(2, "ab".to_string())])
+        );
+        assert_eq!(candidates[1].review.len(), 3);
+
+        assert_eq!(extract(&article("<p>No example.</p>")), vec![]);
+    }
+
+    #[test]
+    fn test_save() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let mut candidate = Candidate {
+            input: "1\n".to_string(),
+            answers: BTreeMap::from([(1, "2".to_string())]),
+            review: vec!["unsure".to_string()],
+        };
+        save(dir, 2024, 1, &[candidate.clone(), candidate.clone()]).unwrap();
+        assert!(needs_examples(dir, 2024, 1));
+        let answers = std::fs::read_to_string(dir.join("y2024/day01/extracted2.answers")).unwrap();
+        assert_eq!(
+            answers,
+            "# Extracted from the puzzle description\n# review: unsure\n1 2\n"
+        );
+
+        // Extracting again replaces what was there before.
+        candidate.answers.insert(2, "3".to_string());
+        save(dir, 2024, 1, &[candidate.clone()]).unwrap();
+        assert!(!needs_examples(dir, 2024, 1));
+        assert!(!dir.join("y2024/day01/extracted2.txt").exists());
+
+        // Even when an example's input was already deleted by hand.
+        std::fs::remove_file(dir.join("y2024/day01/extracted1.txt")).unwrap();
+        save(dir, 2024, 1, &[candidate]).unwrap();
+        assert!(dir.join("y2024/day01/extracted1.txt").exists());
+    }
+}
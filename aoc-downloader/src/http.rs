@@ -0,0 +1,259 @@
+use std::{path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response, StatusCode, cookie::Jar, header::RETRY_AFTER};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::Error;
+
+/// Don't wait any longer than this to retry, even if the server asks to.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Options for how politely to talk to the site.
+#[derive(Debug, Clone, clap::Args)]
+pub struct HttpArgs {
+    /// How to reach you if the downloader misbehaves, such as an email
+    /// address, sent in the User-Agent
+    #[arg(long, env = "AOC_CONTACT", global = true)]
+    pub contact: Option<String>,
+
+    /// The least number of seconds between requests, which is also kept to
+    /// between separate runs
+    #[arg(long, default_value = "5", value_parser = parse_seconds, global = true)]
+    pub min_interval: Duration,
+
+    /// Where the time of the last request is recorded
+    ///
+    /// Defaults to a file in the system's temporary directory.
+    #[arg(long, env = "AOC_THROTTLE_FILE", global = true)]
+    pub throttle_file: Option<PathBuf>,
+
+    /// How many times to retry a download that fails with a 429 or 5xx
+    #[arg(long, default_value_t = 3, global = true)]
+    pub retries: u32,
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|error| format!("invalid number of seconds '{value}': {error}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
+
+impl HttpArgs {
+    pub fn user_agent(&self) -> String {
+        let agent = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+        match &self.contact {
+            Some(contact) => format!("{agent} ({contact})"),
+            None => agent.to_string(),
+        }
+    }
+
+    pub fn throttle(&self) -> Throttle {
+        let state = self
+            .throttle_file
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("aoc-downloader-last-request"));
+        Throttle::new(self.min_interval, Some(state))
+    }
+
+    pub fn retry(&self) -> Retry {
+        Retry {
+            retries: self.retries,
+            initial_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Keeps requests at least `interval` apart, including requests made by
+/// earlier runs when there is a `state` file to record the last one in.
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    state: Option<PathBuf>,
+    last: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration, state: Option<PathBuf>) -> Throttle {
+        Throttle {
+            interval,
+            state,
+            last: Mutex::new(None),
+        }
+    }
+
+    fn read_state(&self) -> Option<DateTime<Utc>> {
+        let data = std::fs::read_to_string(self.state.as_ref()?).ok()?;
+        Some(DateTime::parse_from_rfc3339(data.trim()).ok()?.to_utc())
+    }
+
+    /// Wait until the next request is allowed, and record that it's being made.
+    pub async fn wait(&self) -> Result<(), Error> {
+        let mut last = self.last.lock().await;
+        if let Some(previous) = (*last).max(self.read_state()) {
+            // A last request in the future can only be a wrong clock, which
+            // shouldn't stop requests for longer than the interval.
+            if let Ok(wait) = (previous + self.interval - Utc::now()).to_std() {
+                tokio::time::sleep(wait.min(self.interval)).await;
+            }
+        }
+
+        let now = Utc::now();
+        if let Some(state) = &self.state {
+            std::fs::write(state, now.to_rfc3339())
+                .map_err(|error| format!("{}: {error}", state.display()))?;
+        }
+        *last = Some(now);
+        Ok(())
+    }
+}
+
+/// Retry requests that fail with a 429 or 5xx status, doubling the delay each
+/// time unless the server says how long to wait.
+#[derive(Debug, Clone, Copy)]
+pub struct Retry {
+    pub retries: u32,
+    pub initial_delay: Duration,
+}
+
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok()?.parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// A client for the site, which identifies itself, keeps to the throttle and
+/// retries when the server is struggling.
+#[derive(Debug)]
+pub struct Http {
+    client: Client,
+    throttle: Throttle,
+    retry: Retry,
+}
+
+impl Http {
+    pub fn new(
+        session: &str,
+        base: &Url,
+        user_agent: &str,
+        throttle: Throttle,
+        retry: Retry,
+    ) -> Result<Http, Error> {
+        let cookie_jar = Jar::default();
+        cookie_jar.add_cookie_str(&format!("session={session}"), base);
+        let client = reqwest::ClientBuilder::new()
+            .cookie_provider(cookie_jar.into())
+            .user_agent(user_agent)
+            .build()?;
+        Ok(Http {
+            client,
+            throttle,
+            retry,
+        })
+    }
+
+    async fn send(&self, request: RequestBuilder, retries: u32) -> Result<Response, Error> {
+        let mut delay = self.retry.initial_delay;
+        let mut attempt = 0;
+        loop {
+            self.throttle.wait().await?;
+            let response = request
+                .try_clone()
+                .ok_or("the request can't be retried")?
+                .send()
+                .await?;
+            let status = response.status();
+            if attempt >= retries
+                || !(status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error())
+            {
+                return Ok(response);
+            }
+
+            let wait = delay.max(retry_after(&response).unwrap_or_default());
+            if wait > MAX_RETRY_DELAY {
+                return Ok(response);
+            }
+            eprintln!("{} returned {status}, retrying in {wait:?}", response.url());
+            tokio::time::sleep(wait).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    pub async fn get(&self, url: Url) -> Result<Response, Error> {
+        self.send(self.client.get(url), self.retry.retries).await
+    }
+
+    /// Post a form, which isn't retried since the first attempt may have counted.
+    pub async fn post_form(&self, url: Url, form: &[(&str, String)]) -> Result<Response, Error> {
+        self.send(self.client.post(url).form(form), 0).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_throttle() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = temp.path().join("throttle");
+        let interval = Duration::from_millis(200);
+
+        let start = Instant::now();
+        Throttle::new(interval, Some(state.clone()))
+            .wait()
+            .await
+            .unwrap();
+        // A separate throttle, as in the next run, still waits for the first.
+        let throttle = Throttle::new(interval, Some(state.clone()));
+        throttle.wait().await.unwrap();
+        assert!(start.elapsed() >= interval);
+        throttle.wait().await.unwrap();
+        assert!(start.elapsed() >= interval * 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("GET", "/")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let working = server
+            .mock("GET", "/")
+            .match_header("user-agent", "test")
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let retry = Retry {
+            retries: 2,
+            initial_delay: Duration::from_millis(10),
+        };
+        let throttle = Throttle::new(Duration::ZERO, None);
+        let http = Http::new("abc", &base, "test", throttle, retry).unwrap();
+        let response = http.get(base.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        failing.assert_async().await;
+        working.assert_async().await;
+
+        // Answers aren't retried.
+        let posted = server
+            .mock("POST", "/")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+        let response = http.post_form(base, &[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        posted.assert_async().await;
+    }
+}
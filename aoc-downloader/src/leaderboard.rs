@@ -0,0 +1,457 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{Day, Error, Year, calendar, http::Http};
+
+/// The site asks for private leaderboards to be fetched at most once every 15 minutes.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as served by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for members who haven't set a name.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// When each part of each day was solved.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<Day, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    /// The name the site shows for the member.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star_time(&self, day: Day, part: u8) -> Option<DateTime<Utc>> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        DateTime::from_timestamp(star.get_star_ts, 0)
+    }
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, Error> {
+    Ok(serde_json::from_str(json)?)
+}
+
+fn build_leaderboard_url(base: &Url, year: Year, id: u64) -> Result<Url, Error> {
+    Ok(base.join(&format!("{year}/leaderboard/private/view/{id}.json"))?)
+}
+
+pub fn cache_path(cache: &Path, year: Year, id: u64) -> PathBuf {
+    cache.join(format!("{year}-{id}.json"))
+}
+
+/// Load a leaderboard, from the cache if it was fetched within the last
+/// [`POLL_INTERVAL`], or otherwise from the site, caching it for next time.
+pub async fn load(
+    http: &Http,
+    base: &Url,
+    cache: &Path,
+    year: Year,
+    id: u64,
+) -> Result<Leaderboard, Error> {
+    let path = cache_path(cache, year, id);
+    let age = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    if let Some(age) = age.filter(|age| *age < POLL_INTERVAL) {
+        eprintln!(
+            "Using the leaderboard fetched {} minutes ago, it can be fetched again in {} minutes",
+            age.as_secs() / 60,
+            (POLL_INTERVAL - age).as_secs().div_ceil(60)
+        );
+        return parse(&std::fs::read_to_string(&path)?);
+    }
+
+    let response = http.get(build_leaderboard_url(base, year, id)?).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("could not fetch the leaderboard, HTTP {status}").into());
+    }
+    let json = response.text().await?;
+    // Without a valid session the site redirects to the leaderboard's HTML page.
+    let leaderboard = parse(&json).map_err(|error| {
+        format!("the leaderboard isn't valid JSON, has the session expired? {error}")
+    })?;
+    std::fs::create_dir_all(cache)?;
+    std::fs::write(&path, json)?;
+    Ok(leaderboard)
+}
+
+/// How a member did on one day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayResult {
+    pub day: Day,
+    pub part1: Option<DateTime<Utc>>,
+    pub part2: Option<DateTime<Utc>>,
+    /// Seconds between solving part one and part two.
+    pub part2_delta: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Ranking {
+    pub rank: usize,
+    pub id: u64,
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u32,
+    pub days: Vec<DayResult>,
+}
+
+/// Who did best on one day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DaySummary {
+    pub day: Day,
+    /// The first member to solve both parts.
+    pub first: Option<String>,
+    /// Seconds from the puzzle unlocking to the first member solving both parts.
+    pub first_time: Option<i64>,
+    pub fastest_part2: Option<String>,
+    pub fastest_part2_delta: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub year: Year,
+    pub rankings: Vec<Ranking>,
+    pub days: Vec<DaySummary>,
+}
+
+impl Report {
+    pub fn new(leaderboard: &Leaderboard, year: Year) -> Report {
+        // Ties go to whoever got their last star first, as on the site.
+        let mut members: Vec<&Member> = leaderboard.members.values().collect();
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.local_score),
+                member.last_star_ts,
+                member.id,
+            )
+        });
+
+        let days = 1..=calendar::days_in_event(year);
+        let rankings: Vec<Ranking> = members
+            .iter()
+            .enumerate()
+            .map(|(i, member)| Ranking {
+                rank: i + 1,
+                id: member.id,
+                name: member.display_name(),
+                local_score: member.local_score,
+                stars: member.stars,
+                days: days
+                    .clone()
+                    .map(|day| {
+                        let part1 = member.star_time(day, 1);
+                        let part2 = member.star_time(day, 2);
+                        let part2_delta = part1
+                            .zip(part2)
+                            .map(|(part1, part2)| (part2 - part1).num_seconds());
+                        DayResult {
+                            day,
+                            part1,
+                            part2,
+                            part2_delta,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let days = days
+            .map(|day| {
+                let index = day as usize - 1;
+                let unlock = calendar::unlock_time(year, day);
+                let first = rankings
+                    .iter()
+                    .filter_map(|ranking| Some((ranking.days[index].part2?, &ranking.name)))
+                    .min();
+                let fastest = rankings
+                    .iter()
+                    .filter_map(|ranking| Some((ranking.days[index].part2_delta?, &ranking.name)))
+                    .min();
+                DaySummary {
+                    day,
+                    first: first.map(|(_, name)| name.clone()),
+                    first_time: first
+                        .zip(unlock)
+                        .map(|((time, _), unlock)| (time - unlock).num_seconds()),
+                    fastest_part2: fastest.map(|(_, name)| name.clone()),
+                    fastest_part2_delta: fastest.map(|(delta, _)| delta),
+                }
+            })
+            .collect();
+
+        Report {
+            year,
+            rankings,
+            days,
+        }
+    }
+
+    /// A table of the rankings, with each member's stars per day, followed by
+    /// who did best on each day.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let day_count = self.days.len();
+        let tens: String = (1..=day_count)
+            .map(|day| {
+                if day >= 10 {
+                    char::from(b'0' + (day / 10) as u8)
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        let units: String = (1..=day_count)
+            .map(|day| char::from(b'0' + (day % 10) as u8))
+            .collect();
+        writeln!(text, "Private leaderboard {}", self.year).unwrap();
+        writeln!(text).unwrap();
+        writeln!(text, "{:>4} {:>5} {:>5}  {tens}", "", "", "").unwrap();
+        writeln!(
+            text,
+            "{:>4} {:>5} {:>5}  {units}  Name",
+            "Rank", "Score", "Stars"
+        )
+        .unwrap();
+        for ranking in &self.rankings {
+            let stars: String = ranking
+                .days
+                .iter()
+                .map(|day| match (day.part1, day.part2) {
+                    (_, Some(_)) => '*',
+                    (Some(_), None) => '+',
+                    (None, None) => '.',
+                })
+                .collect();
+            writeln!(
+                text,
+                "{:>4} {:>5} {:>5}  {stars}  {}",
+                ranking.rank, ranking.local_score, ranking.stars, ranking.name
+            )
+            .unwrap();
+        }
+
+        writeln!(text).unwrap();
+        writeln!(
+            text,
+            "{:>3}  {:<24} {:>10}  {:<24} {:>10}",
+            "Day", "First to finish", "Time", "Fastest part two", "Delta"
+        )
+        .unwrap();
+        for day in &self.days {
+            let (Some(first), Some(first_time)) = (&day.first, day.first_time) else {
+                continue;
+            };
+            writeln!(
+                text,
+                "{:>3}  {:<24} {:>10}  {:<24} {:>10}",
+                day.day,
+                first,
+                format_seconds(first_time),
+                day.fastest_part2.as_deref().unwrap_or_default(),
+                day.fastest_part2_delta
+                    .map(format_seconds)
+                    .unwrap_or_default(),
+            )
+            .unwrap();
+        }
+        text
+    }
+
+    /// One row for each member and day.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rank,id,name,local_score,day,part1,part2,part2_delta\n");
+        let time =
+            |time: Option<DateTime<Utc>>| time.map(|time| time.to_rfc3339()).unwrap_or_default();
+        for ranking in &self.rankings {
+            for day in &ranking.days {
+                writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{}",
+                    ranking.rank,
+                    ranking.id,
+                    csv_field(&ranking.name),
+                    ranking.local_score,
+                    day.day,
+                    time(day.part1),
+                    time(day.part2),
+                    day.part2_delta
+                        .map(|delta| delta.to_string())
+                        .unwrap_or_default()
+                )
+                .unwrap();
+            }
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Format a number of seconds as `h:mm:ss`, with days in front if there are any.
+fn format_seconds(seconds: i64) -> String {
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    let time = format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard-2024.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.members.len(), 4);
+        let anonymous = &leaderboard.members["303"];
+        assert_eq!(anonymous.display_name(), "(anonymous user #303)");
+        assert_eq!(
+            anonymous.star_time(1, 1),
+            DateTime::from_timestamp(1733040000, 0)
+        );
+        assert_eq!(anonymous.star_time(1, 2), None);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report::new(&parse(FIXTURE).unwrap(), 2024);
+        let names: Vec<&str> = report
+            .rankings
+            .iter()
+            .map(|ranking| ranking.name.as_str())
+            .collect();
+        // Bob and Alice are tied, but Bob got their last star first.
+        assert_eq!(
+            names,
+            ["Bob, the Builder", "Alice", "(anonymous user #303)", "Dana"]
+        );
+        assert_eq!(report.rankings[0].days[0].part2_delta, Some(520));
+        assert_eq!(report.rankings[0].days[1].part2_delta, None);
+        assert_eq!(report.rankings[0].days.len(), 25);
+
+        assert_eq!(
+            report.days[0],
+            DaySummary {
+                day: 1,
+                first: Some("Alice".to_string()),
+                first_time: Some(600),
+                fastest_part2: Some("Alice".to_string()),
+                fastest_part2_delta: Some(300),
+            }
+        );
+        assert_eq!(report.days[2].first, None);
+
+        let text = report.to_text();
+        assert!(text.contains("   1    10     3  *+.......................  Bob, the Builder\n"));
+        assert!(text.contains(
+            "  2  Alice                       0:15:00  Alice                       0:10:00\n"
+        ));
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 1 + 4 * 25);
+        assert!(csv.contains(
+            "\n1,202,\"Bob, the Builder\",10,1,2024-12-01T05:03:20+00:00,2024-12-01T05:12:00+00:00,520\n"
+        ));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["rankings"][1]["days"][0]["part2_delta"], 300);
+    }
+
+    #[test]
+    fn test_format_seconds() {
+        assert_eq!(format_seconds(65), "0:01:05");
+        assert_eq!(format_seconds(3 * 86400 + 3600), "3d 1:00:00");
+    }
+
+    #[tokio::test]
+    async fn test_load() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/2024/leaderboard/private/view/101.json")
+            .with_body(FIXTURE)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let throttle = crate::http::Throttle::new(Duration::ZERO, None);
+        let retry = crate::http::Retry {
+            retries: 0,
+            initial_delay: Duration::ZERO,
+        };
+        let http = Http::new("abc", &base, "test", throttle, retry).unwrap();
+        let temp = tempfile::tempdir().unwrap();
+        let cache = temp.path();
+
+        // The second load comes from the cache.
+        for _ in 0..2 {
+            let leaderboard = load(&http, &base, cache, 2024, 101).await.unwrap();
+            assert_eq!(leaderboard.members.len(), 4);
+        }
+        mock.assert_async().await;
+
+        // Until the cache is too old.
+        let file = std::fs::File::options()
+            .write(true)
+            .open(cache_path(cache, 2024, 101))
+            .unwrap();
+        file.set_modified(SystemTime::now() - POLL_INTERVAL)
+            .unwrap();
+        let mock = server
+            .mock("GET", "/2024/leaderboard/private/view/101.json")
+            .with_body("<html>Log in</html>")
+            .create_async()
+            .await;
+        assert!(load(&http, &base, cache, 2024, 101).await.is_err());
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,869 @@
+//! Downloading puzzle inputs and talking to the rest of the Advent of Code
+//! site, for the `aoc-downloader` command and for other crates of the
+//! workspace.
+
+use aoc_manifest::{Entry, Manifest};
+use chrono::Utc;
+use clock::Clock;
+use futures::{StreamExt, stream};
+use http::{Http, HttpArgs};
+use progress::Progress;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use submit::{Attempt, History, Verdict};
+use url::Url;
+use validate::InputError;
+
+pub mod calendar;
+pub mod clock;
+pub mod describe;
+pub mod encrypt;
+pub mod examples;
+pub mod http;
+pub mod leaderboard;
+pub mod profile;
+pub mod progress;
+pub mod selection;
+pub mod status;
+pub mod submit;
+pub mod validate;
+
+pub use aoc_common::{Day, Year};
+
+pub type Error = Box<dyn std::error::Error>;
+
+pub const BASE_URL: &str = "https://adventofcode.com/";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "SESSION";
+
+/// Parse the site's base URL, which needs a trailing slash for paths to be
+/// joined onto it rather than replacing its last segment.
+pub fn parse_base_url(value: &str) -> Result<Url, String> {
+    let mut url = Url::parse(value).map_err(|error| format!("invalid URL '{value}': {error}"))?;
+    if url.cannot_be_a_base() {
+        return Err(format!("'{value}' can't be a base URL"));
+    }
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(url)
+}
+
+pub fn build_download_url(base: &Url, year: Year, day: Day) -> Result<Url, Error> {
+    Ok(base.join(&format!("{year}/day/{day}/input"))?)
+}
+
+pub fn build_page_url(base: &Url, year: Year, day: Day) -> Result<Url, Error> {
+    Ok(base.join(&format!("{year}/day/{day}"))?)
+}
+
+/// A download that isn't a puzzle input, with the body so it can be quarantined.
+#[derive(Debug)]
+pub struct Rejected {
+    pub error: InputError,
+    pub body: Vec<u8>,
+}
+
+impl Display for Rejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+pub async fn download_day(http: &Http, base: &Url, year: Year, day: Day) -> Result<Vec<u8>, Error> {
+    let url = build_download_url(base, year, day)?;
+    let response = http.get(url).await?;
+    let status = response.status();
+    let retry_after = http::retry_after(&response);
+    let body = response.bytes().await?.to_vec();
+    match validate::check_download(status, retry_after, &body) {
+        Ok(()) => Ok(body),
+        Err(error) => Err(Rejected { error, body }.into()),
+    }
+}
+
+pub fn input_path(output: &Path, year: Year, day: Day) -> PathBuf {
+    output.join(format!("y{year}")).join(format!("day{day:02}"))
+}
+
+/// The puzzle description is kept next to the input.
+pub fn description_path(output: &Path, year: Year, day: Day) -> PathBuf {
+    input_path(output, year, day).with_extension("md")
+}
+
+/// Whether a day's description is missing, or doesn't have part two yet.
+pub fn needs_description(output: &Path, year: Year, day: Day) -> bool {
+    match std::fs::read_to_string(description_path(output, year, day)) {
+        Ok(markdown) => !describe::has_part_two(&markdown),
+        Err(_) => true,
+    }
+}
+
+/// Save a downloaded input, and record it in the manifest of `output`.
+pub fn save_input(
+    output: &Path,
+    profile: Option<&str>,
+    year: Year,
+    day: Day,
+    data: &[u8],
+) -> Result<PathBuf, Error> {
+    let path = input_path(output, year, day);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, data)?;
+
+    let manifest_path = aoc_manifest::manifest_path(output);
+    let mut manifest = Manifest::load(&manifest_path)
+        .map_err(|error| format!("{}: {error}", manifest_path.display()))?;
+    let entry = Entry::new(year.try_into()?, day.try_into()?, profile, data, Utc::now());
+    manifest.insert(entry);
+    manifest
+        .save()
+        .map_err(|error| format!("{}: {error}", manifest_path.display()))?;
+    Ok(path)
+}
+
+/// Where downloads that aren't puzzle inputs are kept, out of the way of the
+/// real inputs.
+pub fn quarantine_path(output: &Path, year: Year, day: Day) -> PathBuf {
+    input_path(&output.join(aoc_common::QUARANTINE_DIR), year, day)
+}
+
+pub fn quarantine(output: &Path, year: Year, day: Day, body: &[u8]) -> Result<PathBuf, Error> {
+    let path = quarantine_path(output, year, day);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, body)?;
+    Ok(path)
+}
+
+/// The days that have a saved input, from the `y{year}/day{day}` files in `output`.
+pub fn saved_days(output: &Path) -> Result<Vec<(Year, Day)>, Error> {
+    let mut days = vec![];
+    let Ok(year_entries) = std::fs::read_dir(output) else {
+        return Ok(days);
+    };
+    for year_entry in year_entries {
+        let year_entry = year_entry?;
+        let Some(year) = year_entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix('y')?.parse().ok())
+        else {
+            continue;
+        };
+        for day_entry in std::fs::read_dir(year_entry.path())? {
+            let Some(day) = day_entry?
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("day")?.parse().ok())
+            else {
+                continue;
+            };
+            days.push((year, day));
+        }
+    }
+    days.sort();
+    Ok(days)
+}
+
+/// Move saved inputs that are really error pages into quarantine, so that
+/// they're downloaded again, or only report them if `dry_run` is set.
+/// Returns the days that were invalid.
+pub fn revalidate(output: &Path, dry_run: bool) -> Result<Vec<(Year, Day)>, Error> {
+    let mut invalid = vec![];
+    for (year, day) in saved_days(output)? {
+        let path = input_path(output, year, day);
+        let data = std::fs::read(&path)?;
+        if let Err(error) = validate::check_input(&data) {
+            let quarantine_path = quarantine_path(output, year, day);
+            if dry_run {
+                eprintln!("{path:?}: {error}, would move it to {quarantine_path:?}");
+            } else {
+                std::fs::create_dir_all(quarantine_path.parent().unwrap())?;
+                std::fs::rename(&path, &quarantine_path)?;
+                eprintln!("{path:?}: {error}, moved it to {quarantine_path:?}");
+            }
+            invalid.push((year, day));
+        }
+    }
+    Ok(invalid)
+}
+
+/// What became of a day's download.
+enum Outcome {
+    Saved {
+        path: PathBuf,
+        bytes: usize,
+    },
+    Failed(String),
+    /// A failure that every other day would share, such as an expired session.
+    Fatal(String),
+}
+
+async fn try_download_one(
+    http: &Http,
+    base: &Url,
+    output: &Path,
+    profile: Option<&str>,
+    year: Year,
+    day: Day,
+) -> Result<Outcome, Error> {
+    let data = match download_day(http, base, year, day).await {
+        Ok(data) => data,
+        Err(error) => {
+            let rejected = error.downcast::<Rejected>()?;
+            let path = quarantine(output, year, day, &rejected.body)?;
+            let message = format!(
+                "Could not download {year}:{day:02}, {}. The response is in {path:?}.",
+                rejected.error
+            );
+            return Ok(match rejected.error.is_fatal() {
+                true => Outcome::Fatal(message),
+                false => Outcome::Failed(message),
+            });
+        }
+    };
+    let path = save_input(output, profile, year, day, &data)?;
+    Ok(Outcome::Saved {
+        path,
+        bytes: data.len(),
+    })
+}
+
+async fn download_one(
+    http: &Http,
+    base: &Url,
+    output: &Path,
+    profile: Option<&str>,
+    year: Year,
+    day: Day,
+) -> Outcome {
+    try_download_one(http, base, output, profile, year, day)
+        .await
+        .unwrap_or_else(|error| {
+            Outcome::Failed(format!("Could not download {year}:{day:02}, {error}"))
+        })
+}
+
+/// Count an outcome, returning whether to carry on with the other days.
+fn record(progress: &mut Progress, outcome: Outcome) -> bool {
+    match outcome {
+        Outcome::Saved { path, bytes } => {
+            progress.downloaded += 1;
+            progress.message(&format!("Wrote {bytes} bytes to {path:?}"));
+            true
+        }
+        Outcome::Failed(message) => {
+            progress.failed += 1;
+            progress.message(&message);
+            true
+        }
+        Outcome::Fatal(message) => {
+            progress.failed += 1;
+            progress.message(&message);
+            false
+        }
+    }
+}
+
+/// Download each day, replacing any saved input, with up to `jobs` downloads
+/// at once. Requests still go through the throttle one at a time, so this
+/// only overlaps the waiting on responses.
+///
+/// The first day is downloaded on its own, so that an expired session stops
+/// the run before any others are started. Returns whether they all succeeded.
+pub async fn download_days(
+    http: &Http,
+    base: &Url,
+    output: &Path,
+    profile: Option<&str>,
+    days: &[(Year, Day)],
+    jobs: usize,
+    progress: &mut Progress,
+) -> bool {
+    let failed = progress.failed;
+    let Some((&(year, day), rest)) = days.split_first() else {
+        return true;
+    };
+    progress.redraw();
+    if !record(
+        progress,
+        download_one(http, base, output, profile, year, day).await,
+    ) {
+        return false;
+    }
+
+    let mut downloads = stream::iter(rest)
+        .map(|&(year, day)| download_one(http, base, output, profile, year, day))
+        .buffer_unordered(jobs.max(1));
+    while let Some(outcome) = downloads.next().await {
+        if !record(progress, outcome) {
+            return false;
+        }
+    }
+    progress.failed == failed
+}
+
+/// Download each day's puzzle page, saving its description as Markdown if
+/// `descriptions` is set, and extracting its examples into `examples` if
+/// given. Returns whether they all succeeded.
+pub async fn download_pages(
+    http: &Http,
+    base: &Url,
+    output: &Path,
+    days: &[(Year, Day)],
+    descriptions: bool,
+    examples: Option<&Path>,
+) -> Result<bool, Error> {
+    let mut success = true;
+    for &(year, day) in days {
+        let (url, page) = match describe::download_page(http, base, year, day).await {
+            Ok(page) => page,
+            Err(error) => {
+                eprintln!("Could not download the puzzle page of {year}:{day:02}, {error}");
+                success = false;
+                continue;
+            }
+        };
+
+        if descriptions {
+            let Some(markdown) = describe::page_to_markdown(&page, &url) else {
+                eprintln!("There is no puzzle description on the page of {year}:{day:02}");
+                success = false;
+                continue;
+            };
+            let path = description_path(output, year, day);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(&path, markdown)?;
+            eprintln!("Wrote the description of {year}:{day:02} to {path:?}");
+        }
+
+        if let Some(examples) = examples {
+            let candidates = examples::extract(&page);
+            let dir = examples::save(examples, year, day, &candidates)?;
+            let unsure = candidates
+                .iter()
+                .filter(|candidate| !candidate.review.is_empty())
+                .count();
+            eprintln!(
+                "Extracted {} examples of {year}:{day:02} into {dir:?}, {unsure} of which need reviewing",
+                candidates.len()
+            );
+        }
+    }
+    Ok(success)
+}
+
+pub fn build_http(session: &str, base: &Url, args: &HttpArgs) -> Result<Http, Error> {
+    if args.contact.is_none() {
+        eprintln!(
+            "warning: no --contact given, so the site can't tell who to contact about requests"
+        );
+    }
+    Http::new(
+        session,
+        base,
+        &args.user_agent(),
+        args.throttle(),
+        args.retry(),
+    )
+}
+
+/// Downloads inputs without an async runtime of its own, for programs that
+/// only want the odd input.
+pub struct Downloader {
+    runtime: tokio::runtime::Runtime,
+    http: Http,
+    base: Url,
+    /// Recorded in the manifest of each input.
+    profile: Option<String>,
+}
+
+impl Downloader {
+    pub fn new(http: Http, base: Url, profile: Option<&str>) -> Result<Downloader, Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Downloader {
+            runtime,
+            http,
+            base,
+            profile: profile.map(str::to_string),
+        })
+    }
+
+    /// Configured from the same environment variables as the command, with
+    /// the session of `profile` if one is given. `None` if there's no session
+    /// to download with.
+    pub fn from_env(profile: Option<&str>) -> Result<Option<Downloader>, Error> {
+        #[derive(clap::Parser)]
+        struct EnvArgs {
+            #[command(flatten)]
+            http: HttpArgs,
+        }
+
+        let profiles = std::env::var_os(profile::PROFILES_VAR)
+            .map_or_else(|| PathBuf::from(profile::PROFILES_FILE), PathBuf::from);
+        let session = std::env::var(SESSION_VAR).ok();
+        let Some(session) = profile::resolve_session(&profiles, profile, session)? else {
+            return Ok(None);
+        };
+        let base = match std::env::var(BASE_URL_VAR) {
+            Ok(base) => parse_base_url(&base)?,
+            Err(_) => Url::parse(BASE_URL)?,
+        };
+        let args = <EnvArgs as clap::Parser>::try_parse_from([env!("CARGO_PKG_NAME")])?;
+        let http = build_http(&session, &base, &args.http)?;
+        Downloader::new(http, base, profile).map(Some)
+    }
+
+    /// Download a day's input into `output`, returning where it was saved.
+    pub fn download_input(&self, output: &Path, year: Year, day: Day) -> Result<PathBuf, Error> {
+        let data = self
+            .runtime
+            .block_on(download_day(&self.http, &self.base, year, day))?;
+        let path = save_input(output, self.profile.as_deref(), year, day, &data)?;
+        eprintln!("Wrote {} bytes to {path:?}", data.len());
+        Ok(path)
+    }
+
+    /// Fetch the stars earned on each day of `year`.
+    pub fn fetch_stars(&self, year: Year) -> Result<BTreeMap<Day, u8>, Error> {
+        self.runtime
+            .block_on(status::fetch_stars(&self.http, &self.base, year))
+    }
+
+    /// Wait for the next puzzle to unlock and download its input into
+    /// `output`, as [`wait_for_unlock`] does.
+    pub fn wait_for_unlock(
+        &self,
+        output: &Path,
+        grace: Duration,
+    ) -> Result<(Year, Day, bool), Error> {
+        self.runtime.block_on(wait_for_unlock(
+            &clock::SystemClock,
+            &self.http,
+            &self.base,
+            output,
+            self.profile.as_deref(),
+            grace,
+        ))
+    }
+}
+
+/// Sleep until the next puzzle unlocks, then download its input, retrying
+/// with backoff until `grace` after the unlock. Returns the day, and whether
+/// its input was saved.
+pub async fn wait_for_unlock(
+    clock: &impl Clock,
+    http: &Http,
+    base: &Url,
+    output: &Path,
+    profile: Option<&str>,
+    grace: Duration,
+) -> Result<(Year, Day, bool), Error> {
+    let (year, day, unlock) = calendar::next_unlock(clock.now());
+    eprintln!(
+        "Waiting for {year}:{day:02}, which unlocks at {}",
+        unlock.with_timezone(&calendar::release_timezone())
+    );
+    clock.sleep_until(unlock).await;
+
+    let deadline = unlock + grace;
+    let mut delay = Duration::from_secs(1);
+    loop {
+        let error = match download_day(http, base, year, day).await {
+            Ok(data) => {
+                let output_path = save_input(output, profile, year, day, &data)?;
+                let bytes = data.len();
+                eprintln!("Wrote {bytes} bytes to {output_path:?}");
+                return Ok((year, day, true));
+            }
+            Err(error) => error,
+        };
+
+        // The server's clock can be a little behind, and it may be busy right
+        // after an unlock, so anything but an expired session is worth retrying.
+        let rejected = error.downcast::<Rejected>();
+        if let Ok(Rejected {
+            error:
+                InputError::RateLimited {
+                    retry_after: Some(retry_after),
+                },
+            ..
+        }) = rejected.as_deref()
+        {
+            delay = delay.max(*retry_after);
+        }
+        let retry_at = clock.now() + delay;
+        let give_up = retry_at > deadline
+            || matches!(&rejected, Ok(rejected) if rejected.error == InputError::SessionExpired);
+        match rejected {
+            Ok(rejected) if give_up => {
+                let path = quarantine(output, year, day, &rejected.body)?;
+                eprintln!(
+                    "Could not download {year}:{day:02}, {}. The response is in {path:?}.",
+                    rejected.error
+                );
+                return Ok((year, day, false));
+            }
+            Err(error) if give_up => {
+                eprintln!("Could not download {year}:{day:02}, {error}");
+                return Ok((year, day, false));
+            }
+            Ok(rejected) => eprintln!(
+                "Could not download {year}:{day:02} yet, {}. Retrying in {delay:?}.",
+                rejected.error
+            ),
+            Err(error) => {
+                eprintln!("Could not download {year}:{day:02} yet, {error}. Retrying in {delay:?}.")
+            }
+        }
+        clock.sleep_until(retry_at).await;
+        delay *= 2;
+    }
+}
+
+/// Returns whether the answer is known to be right.
+pub async fn submit(
+    http: &Http,
+    base: &Url,
+    history: &mut History,
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<bool, Error> {
+    for warning in history.check(year, day, part, answer)? {
+        eprintln!("warning: {warning}");
+    }
+
+    let verdict = submit::submit_answer(http, base, year, day, part, answer).await?;
+    history.record(Attempt {
+        time: Utc::now(),
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+    })?;
+
+    match verdict {
+        Verdict::Correct => eprintln!("{answer} is the right answer"),
+        Verdict::TooHigh => eprintln!("{answer} is too high"),
+        Verdict::TooLow => eprintln!("{answer} is too low"),
+        Verdict::Wrong => eprintln!("{answer} is not the right answer"),
+        Verdict::RateLimited { wait } => eprintln!(
+            "An answer was submitted too recently, try again in {}s",
+            wait.as_secs()
+        ),
+        Verdict::AlreadySolved => eprintln!("{year} day {day} part {part} is already solved"),
+    }
+    Ok(matches!(verdict, Verdict::Correct | Verdict::AlreadySolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn test_http(base: &Url) -> Http {
+        let throttle = http::Throttle::new(Duration::ZERO, None);
+        let retry = http::Retry {
+            retries: 0,
+            initial_delay: Duration::ZERO,
+        };
+        Http::new("abc", base, "test", throttle, retry).unwrap()
+    }
+
+    #[test]
+    fn test_parse_base_url() {
+        let base = parse_base_url("http://127.0.0.1:1234/aoc").unwrap();
+        assert_eq!(
+            build_download_url(&base, 2024, 1).unwrap().as_str(),
+            "http://127.0.0.1:1234/aoc/2024/day/1/input"
+        );
+        assert_eq!(parse_base_url(BASE_URL).unwrap().as_str(), BASE_URL);
+        assert!(parse_base_url("mailto:someone@example.com").is_err());
+        assert!(parse_base_url("adventofcode.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_unlock() {
+        let mut server = mockito::Server::new_async().await;
+        let locked = server
+            .mock("GET", "/2025/day/3/input")
+            .with_status(404)
+            .with_body("Please don't repeatedly request this endpoint before it unlocks!")
+            .expect(2)
+            .create_async()
+            .await;
+        let unlocked = server
+            .mock("GET", "/2025/day/3/input")
+            .with_body("1 2\n")
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let http = test_http(&base);
+        let temp = tempfile::tempdir().unwrap();
+        let output = temp.path();
+        let start = DateTime::parse_from_rfc3339("2025-12-02T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let clock = clock::FakeClock::new(start);
+
+        let result = wait_for_unlock(&clock, &http, &base, output, None, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(result, (2025, 3, true));
+        locked.assert_async().await;
+        unlocked.assert_async().await;
+        assert_eq!(
+            std::fs::read(input_path(output, 2025, 3)).unwrap(),
+            b"1 2\n"
+        );
+        // It retried one second and then two seconds after the unlock.
+        assert_eq!(
+            clock.now(),
+            DateTime::parse_from_rfc3339("2025-12-03T05:00:03Z").unwrap()
+        );
+
+        // Once the grace period is over, it gives up.
+        let clock = clock::FakeClock::new(start);
+        server
+            .mock("GET", "/2025/day/3/input")
+            .with_status(404)
+            .with_body("Please don't repeatedly request this endpoint before it unlocks!")
+            .create_async()
+            .await;
+        let result = wait_for_unlock(&clock, &http, &base, output, None, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(result, (2025, 3, false));
+        assert!(quarantine_path(output, 2025, 3).exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_days() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/2024/day/1/input")
+            .with_body("1 2\n")
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/2024/day/2/input")
+            .with_status(200)
+            .with_body("<!DOCTYPE html>\n<html><body>Oops</body></html>")
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/2024/day/3/input")
+            .with_status(400)
+            .with_body("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n")
+            .create_async()
+            .await;
+        let never_requested = server
+            .mock("GET", "/2024/day/4/input")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let http = test_http(&base);
+        let temp = tempfile::tempdir().unwrap();
+        let output = temp.path();
+        let days = [(2024, 1), (2024, 2), (2024, 3), (2024, 4)];
+
+        let mut progress = Progress::new(days.len());
+        let success =
+            download_days(&http, &base, output, Some("work"), &days, 1, &mut progress).await;
+        assert!(!success);
+        assert_eq!((progress.downloaded, progress.failed), (1, 2));
+        // The expired session stops the downloads.
+        never_requested.assert_async().await;
+
+        assert_eq!(
+            std::fs::read(input_path(output, 2024, 1)).unwrap(),
+            b"1 2\n"
+        );
+        assert!(!input_path(output, 2024, 2).exists());
+        assert!(quarantine_path(output, 2024, 2).exists());
+        assert!(quarantine_path(output, 2024, 3).exists());
+
+        let manifest = Manifest::load(aoc_manifest::manifest_path(output)).unwrap();
+        let entry = manifest.get(2024, 1).unwrap();
+        assert_eq!(entry.profile.as_deref(), Some("work"));
+        assert_eq!(entry.sha256, aoc_manifest::sha256(b"1 2\n"));
+        assert!(manifest.get(2024, 2).is_none());
+
+        // An error page saved before inputs were checked is found again.
+        std::fs::write(input_path(output, 2024, 5), "404 Not Found\n").unwrap();
+        std::fs::write(input_path(output, 2024, 6), "Please log in").unwrap();
+        assert_eq!(revalidate(output, true).unwrap(), [(2024, 5), (2024, 6)]);
+        assert!(input_path(output, 2024, 5).exists());
+        assert_eq!(revalidate(output, false).unwrap(), [(2024, 5), (2024, 6)]);
+        assert!(!input_path(output, 2024, 5).exists());
+        assert!(!input_path(output, 2024, 6).exists());
+        assert!(quarantine_path(output, 2024, 6).exists());
+        assert!(input_path(output, 2024, 1).exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_days_at_once() {
+        let mut server = mockito::Server::new_async().await;
+        for day in 1..=6 {
+            let mock = server.mock("GET", format!("/2024/day/{day}/input").as_str());
+            let mock = match day {
+                3 => mock.with_status(500).with_body("Internal Server Error"),
+                _ => mock.with_body(format!("{day}\n")),
+            };
+            mock.create_async().await;
+        }
+
+        let base = Url::parse(&server.url()).unwrap();
+        let http = test_http(&base);
+        let temp = tempfile::tempdir().unwrap();
+        let output = temp.path();
+        let days: Vec<_> = (1..=6).map(|day| (2024, day)).collect();
+
+        // A day that fails doesn't stop the others.
+        let mut progress = Progress::new(days.len());
+        let success = download_days(&http, &base, output, None, &days, 3, &mut progress).await;
+        assert!(!success);
+        assert_eq!((progress.downloaded, progress.failed), (5, 1));
+        assert_eq!(saved_days(output).unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_download_pages() {
+        let mut server = mockito::Server::new_async().await;
+        let part_one = "<main><article class=\"day-desc\"><h2>--- Day 2: Test ---</h2><p>One</p><pre><code>1\n</code></pre><p>It's <code><em>2</em></code>.</p></article></main>";
+        let part_two = "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Now <code><em>3</em></code>.</p></article>";
+        let unsolved = server
+            .mock("GET", "/2024/day/2")
+            .with_body(part_one)
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/2024/day/3")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let http = test_http(&base);
+        let temp = tempfile::tempdir().unwrap();
+        let output = temp.path();
+        let examples = output.join("examples");
+
+        let days = [(2024, 2), (2024, 3)];
+        assert!(
+            !download_pages(&http, &base, output, &days, true, Some(&examples))
+                .await
+                .unwrap()
+        );
+        unsolved.assert_async().await;
+        missing.assert_async().await;
+        let markdown = std::fs::read_to_string(description_path(output, 2024, 2)).unwrap();
+        assert_eq!(
+            markdown,
+            "## --- Day 2: Test ---\n\nOne\n\n```\n1\n```\n\nIt's *`2`*.\n"
+        );
+        assert!(needs_description(output, 2024, 2));
+        assert!(examples::needs_examples(&examples, 2024, 2));
+        let example = examples.join("y2024/day02/extracted1");
+        assert_eq!(
+            std::fs::read(example.with_extension("txt")).unwrap(),
+            b"1\n"
+        );
+        // The description isn't mistaken for an input.
+        assert_eq!(saved_days(output).unwrap(), vec![]);
+
+        // Once part one is solved, downloading again picks up part two.
+        server
+            .mock("GET", "/2024/day/2")
+            .with_body(format!("{part_one}{part_two}"))
+            .create_async()
+            .await;
+        assert!(
+            download_pages(&http, &base, output, &days[..1], true, Some(&examples))
+                .await
+                .unwrap()
+        );
+        assert!(!needs_description(output, 2024, 2));
+        assert!(!examples::needs_examples(&examples, 2024, 2));
+        assert_eq!(
+            std::fs::read_to_string(example.with_extension("answers")).unwrap(),
+            "# Extracted from the puzzle description\n1 2\n2 3\n"
+        );
+    }
+
+    #[test]
+    fn test_downloader() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/2024/day/1/input")
+            .with_body("1 2\n")
+            .create();
+        server
+            .mock("GET", "/2024/day/2/input")
+            .with_status(400)
+            .with_body("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n")
+            .create();
+
+        let base = Url::parse(&server.url()).unwrap();
+        let downloader = Downloader::new(test_http(&base), base, None).unwrap();
+        let temp = tempfile::tempdir().unwrap();
+        let output = temp.path();
+        let path = downloader.download_input(output, 2024, 1).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 2\n");
+
+        let error = downloader.download_input(output, 2024, 2).unwrap_err();
+        assert_eq!(
+            error.downcast::<Rejected>().unwrap().error,
+            InputError::SessionExpired
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/2024/day/1/answer")
+            .match_header("cookie", "session=abc")
+            .match_body("level=2&answer=42")
+            .with_body(
+                "<article><p>That's not the right answer; your answer is too low.</p></article>",
+            )
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let http = test_http(&base);
+        let temp = tempfile::tempdir().unwrap();
+        let log = temp.path().join("submissions.txt");
+        let mut history = History::load(&log).unwrap();
+
+        let correct = submit(&http, &base, &mut history, 2024, 1, 2, "42")
+            .await
+            .unwrap();
+        assert!(!correct);
+        mock.assert_async().await;
+
+        // The verdict is recorded, so the same answer isn't sent again.
+        let history = History::load(&log).unwrap();
+        assert_eq!(
+            history.attempts(2024, 1, 2).next().unwrap().verdict,
+            Verdict::TooLow
+        );
+        assert!(history.check(2024, 1, 2, "42").is_err());
+    }
+}
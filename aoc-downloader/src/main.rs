@@ -1,102 +1,428 @@
-use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
-use clap::Parser;
-use reqwest::{Client, cookie::Jar};
-use std::path::PathBuf;
+use aoc_downloader::{
+    BASE_URL, BASE_URL_VAR, Day, Error, Year, build_http, build_page_url, calendar,
+    clock::SystemClock,
+    download_days, download_pages, encrypt, examples,
+    http::HttpArgs,
+    input_path, leaderboard, needs_description, parse_base_url,
+    profile::{
+        self, PROFILE_VAR, PROFILES_FILE, PROFILES_VAR, Profile, Profiles, parse_profile,
+        profile_dir, resolve_session,
+    },
+    progress::Progress,
+    revalidate,
+    selection::Selection,
+    submit,
+    submit::History,
+    wait_for_unlock,
+};
+use chrono::Utc;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum, error::ErrorKind};
+use std::{
+    path::{Path, PathBuf},
+    process::exit,
+    time::Duration,
+};
 use url::Url;
 
-type Year = i32;
-type Day = u32;
-type Error = Box<dyn std::error::Error>;
-
-fn build_download_url(year: Year, day: Day) -> Result<Url, Error> {
-    Ok(Url::parse(&format!(
-        "https://adventofcode.com/{year}/day/{day}/input"
-    ))?)
-}
-
-async fn download_day(client: &Client, year: Year, day: Day) -> Result<Vec<u8>, Error> {
-    let url = build_download_url(year, day)?;
-    let body = client.get(url).send().await?.bytes().await?;
-    Ok(body.to_vec())
-}
-
-fn build_client(session: &str) -> Result<Client, Error> {
-    let cookie_jar = Jar::default();
-    cookie_jar.add_cookie_str(
-        &format!("session={session}"),
-        &Url::parse("https://adventofcode.com")?,
-    );
-    Ok(reqwest::ClientBuilder::new()
-        .cookie_provider(cookie_jar.into())
-        .build()?)
-}
-
-fn all_puzzle_days(until_year: Year, until_day: Day) -> Vec<(Year, Day)> {
-    let start_year = 2014;
-    let start_short_year = 2025;
-
-    let mut puzzle_days = vec![];
-
-    for year in start_year..=until_year {
-        let day_count = if year == until_year {
-            until_day
-        } else if year >= start_short_year {
-            12
-        } else {
-            24
-        };
-        for day in 1..=day_count {
-            puzzle_days.push((year, day));
-        }
+/// The session of the profile if one was chosen, or else the one given.
+fn require_session(
+    profiles: &Path,
+    profile: Option<&str>,
+    session: Option<String>,
+) -> Result<String, Error> {
+    match resolve_session(profiles, profile, session)? {
+        Some(session) => Ok(session),
+        None => Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--session or --profile is required",
+            )
+            .exit(),
     }
-
-    puzzle_days
 }
 
 #[derive(Parser)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The site to talk to, such as a local stand-in for testing
+    #[arg(long, env = BASE_URL_VAR, default_value = BASE_URL, value_parser = parse_base_url, global = true)]
+    base_url: Url,
+
     /// Path to output data to
-    output: PathBuf,
+    ///
+    /// Required unless a subcommand is given
+    output: Option<PathBuf>,
 
     /// The session key
     ///
     /// Grab from the browser
     #[arg(env)]
-    session: String,
+    session: Option<String>,
+
+    /// Use the session of this profile instead, and keep its inputs in their
+    /// own directory under OUTPUT
+    #[arg(long, env = PROFILE_VAR, global = true, value_parser = parse_profile)]
+    profile: Option<String>,
+
+    /// The profiles, one `<name> <session> [<expires>]` line each, where
+    /// the expiry is a date such as 2026-12-26
+    #[arg(long, env = PROFILES_VAR, default_value = PROFILES_FILE, global = true)]
+    profiles: PathBuf,
+
+    #[command(flatten)]
+    selection: Selection,
+
+    #[command(flatten)]
+    http: HttpArgs,
+
+    /// How many inputs to download at once
+    #[arg(long, short, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+
+    /// Download inputs again even if they are already saved
+    #[arg(long)]
+    force: bool,
+
+    /// List what would be downloaded without downloading anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// First move saved inputs that are really error pages into quarantine,
+    /// so they're downloaded again
+    #[arg(long)]
+    revalidate: bool,
+
+    /// Also download puzzle descriptions as Markdown, next to the inputs
+    ///
+    /// Descriptions without part two are downloaded again, to pick it up
+    /// once part one is solved.
+    #[arg(long)]
+    descriptions: bool,
+
+    /// Also extract example inputs and their answers from the puzzle pages
+    /// into DIR, laid out like the workspace's examples directory
+    ///
+    /// Examples the heuristics are unsure about are marked for review, and
+    /// are ignored by the tests until the mark is removed.
+    #[arg(long, value_name = "DIR")]
+    examples: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Submit an answer and record the verdict
+    Submit {
+        year: Year,
+
+        day: Day,
+
+        /// Which part the answer is for, 1 or 2
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        answer: String,
+
+        /// The session key
+        #[arg(long, env)]
+        session: Option<String>,
+
+        /// Where every submitted answer and its verdict is recorded
+        #[arg(long, default_value = "submissions.txt")]
+        log: PathBuf,
+    },
+    /// Wait for the next puzzle to unlock and download its input straight away
+    ///
+    /// The `wait` command of the solutions binary does the same and then
+    /// runs the new day's solution.
+    Wait {
+        /// Path to output data to
+        output: PathBuf,
+
+        /// The session key
+        #[arg(long, env)]
+        session: Option<String>,
+
+        /// How many seconds to keep retrying for after the unlock
+        #[arg(long, default_value_t = 60)]
+        grace: u64,
+    },
+    /// Report on a private leaderboard
+    Leaderboard {
+        /// The leaderboard's id, which is in its URL
+        id: u64,
+
+        /// The event to report on, the latest one by default
+        #[arg(long)]
+        year: Option<Year>,
+
+        /// The session key
+        #[arg(long, env)]
+        session: Option<String>,
+
+        /// Where fetched leaderboards are kept, since they can only be
+        /// fetched every 15 minutes
+        #[arg(long, default_value = "leaderboards")]
+        cache: PathBuf,
+
+        /// How to print the report
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Encrypt the saved inputs and answers into the vault, so they can be
+    /// committed
+    Encrypt {
+        /// Where the inputs were saved
+        output: PathBuf,
+
+        /// The vault to encrypt them into, the workspace's by default
+        #[arg(long, env = "AOC_VAULT_DIR")]
+        vault: Option<PathBuf>,
+
+        /// An age identity file to encrypt with, instead of a passphrase from
+        /// AOC_PASSPHRASE. Prefer one, the vault is public and a passphrase
+        /// must withstand offline guessing
+        #[arg(long, env = "AOC_KEY_FILE")]
+        key_file: Option<PathBuf>,
+    },
+    /// Check that sessions are logged in, either the profile's or the one
+    /// given, or else those of every profile
+    Whoami {
+        /// The session key
+        #[arg(long, env)]
+        session: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Csv,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let args = Args::parse();
+    let base = args.base_url;
+
+    match args.command {
+        Some(Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            session,
+            log,
+        }) => {
+            let session = require_session(&args.profiles, args.profile.as_deref(), session)?;
+            let http = build_http(&session, &base, &args.http)?;
+            let mut history = History::load(&log)?;
+            if !submit(&http, &base, &mut history, year, day, part, &answer).await? {
+                exit(1)
+            }
+            return Ok(());
+        }
+        Some(Command::Wait {
+            output,
+            session,
+            grace,
+        }) => {
+            let profile = args.profile.as_deref();
+            let session = require_session(&args.profiles, profile, session)?;
+            let http = build_http(&session, &base, &args.http)?;
+            let grace = Duration::from_secs(grace);
+            let profile_output = profile_dir(&output, profile);
+            let (_, _, saved) =
+                wait_for_unlock(&SystemClock, &http, &base, &profile_output, profile, grace)
+                    .await?;
+            if !saved {
+                exit(1)
+            }
+            return Ok(());
+        }
+        Some(Command::Leaderboard {
+            id,
+            year,
+            session,
+            cache,
+            format,
+        }) => {
+            let year = year.unwrap_or_else(|| calendar::latest_event(Utc::now()));
+            let session = require_session(&args.profiles, args.profile.as_deref(), session)?;
+            let http = build_http(&session, &base, &args.http)?;
+            let leaderboard = leaderboard::load(&http, &base, &cache, year, id).await?;
+            let report = leaderboard::Report::new(&leaderboard, year);
+            match format {
+                Format::Text => print!("{}", report.to_text()),
+                Format::Json => println!("{}", report.to_json()?),
+                Format::Csv => print!("{}", report.to_csv()),
+            }
+            return Ok(());
+        }
+        Some(Command::Encrypt {
+            output,
+            vault,
+            key_file,
+        }) => {
+            let profile = args.profile.as_deref();
+            let key = encrypt::load_key(key_file.as_deref())?;
+            let vault = profile_dir(&vault.unwrap_or_else(aoc_vault::vault_dir), profile);
+            let answers = profile::answers_path(&output, profile);
+            let output = profile_dir(&output, profile);
+            let encrypted = encrypt::encrypt_inputs(&output, &answers, &vault, &key)?;
+            eprintln!(
+                "{} files encrypted into {}, {} unchanged",
+                encrypted.written,
+                vault.display(),
+                encrypted.unchanged
+            );
+            return Ok(());
+        }
+        Some(Command::Whoami { session }) => {
+            let profiles = Profiles::load(&args.profiles)?;
+            let checks = match (&args.profile, session) {
+                (Some(name), _) => vec![profiles.get(name)?.clone()],
+                (None, Some(session)) => vec![Profile {
+                    name: "session".to_string(),
+                    session,
+                    expires: None,
+                }],
+                (None, None) if profiles.profiles.is_empty() => {
+                    return Err(format!(
+                        "no session was given, and there are no profiles in {}",
+                        args.profiles.display()
+                    )
+                    .into());
+                }
+                (None, None) => profiles.profiles,
+            };
 
-    let client = build_client(&args.session)?;
+            let mut valid = true;
+            for profile in checks {
+                let http = build_http(&profile.session, &base, &args.http)?;
+                match profile::whoami(&http, &base).await {
+                    Ok(user) => {
+                        let expiry = profile
+                            .expires
+                            .map(|date| format!(", and the session expires on {date}"))
+                            .unwrap_or_default();
+                        println!("{}: logged in as {user}{expiry}", profile.name);
+                    }
+                    Err(error) => {
+                        println!("{}: {error}", profile.name);
+                        valid = false;
+                    }
+                }
+            }
+            if !valid {
+                exit(1)
+            }
+            return Ok(());
+        }
+        None => {}
+    }
 
-    // Find out when it is, in UTC-5 as puzzles are released at midnight UTC-5
-    let timezone = FixedOffset::west(60 * 60 * 5);
-    let now = Utc::now().with_timezone(&timezone);
+    // These aren't marked as required since the session can come from the
+    // environment when a subcommand is used, and isn't needed for a dry run.
+    let Some(output) = args.output else {
+        Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "<OUTPUT> is required to download inputs",
+            )
+            .exit()
+    };
+    let profile = args.profile.as_deref();
+    let session = match resolve_session(&args.profiles, profile, args.session)? {
+        Some(session) => Some(session),
+        None if args.dry_run => None,
+        None => Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "<SESSION> or --profile is required to download inputs",
+            )
+            .exit(),
+    };
+    let output = profile_dir(&output, profile);
 
-    eprintln!("{}", now);
+    let now = Utc::now();
+    eprintln!("{}", now.with_timezone(&calendar::release_timezone()));
 
-    // Collect all puzzle days up to now
-    let puzzle_days = all_puzzle_days(now.year(), now.day());
+    let invalid_days = if args.revalidate {
+        let invalid_days = revalidate(&output, args.dry_run)?;
+        eprintln!("Found {} invalid inputs", invalid_days.len());
+        invalid_days
+    } else {
+        vec![]
+    };
 
-    for (year, day) in puzzle_days {
-        // let data = download_day(&client, year, day).await?;
-        let output_path = args
-            .output
-            .join(format!("y{year}"))
-            .join(format!("day{day:02}"));
-        if output_path.exists() {
-            eprintln!("Data exists for {year}:{day:02}, skipping.");
+    // Collect the selected puzzle days up to now
+    let mut days = vec![];
+    let mut pages = vec![];
+    let mut skipped = 0;
+    for (year, day) in calendar::unlocked_days(now) {
+        if !args.selection.matches(year, day) {
+            continue;
+        }
+        let wanted = (args.descriptions && needs_description(&output, year, day))
+            || (args.examples.as_deref())
+                .is_some_and(|examples| examples::needs_examples(examples, year, day));
+        if (args.descriptions || args.examples.is_some()) && (args.force || wanted) {
+            pages.push((year, day));
+        }
+        let saved = input_path(&output, year, day).exists() && !invalid_days.contains(&(year, day));
+        if saved && !args.force {
+            skipped += 1;
             continue;
         }
+        days.push((year, day));
+    }
 
-        std::fs::create_dir_all(output_path.parent().unwrap())?;
-        let data = download_day(&client, year, day).await?;
-        std::fs::write(&output_path, &data)?;
-        let bytes = data.len();
-        eprintln!("Wrote {bytes} bytes to {output_path:?}");
+    if days.is_empty() && pages.is_empty() {
+        eprintln!("Nothing to download, {skipped} inputs are already saved");
+        return Ok(());
+    }
+    if args.dry_run {
+        for (year, day) in days {
+            println!(
+                "{year}:{day:02} {}",
+                input_path(&output, year, day).display()
+            );
+        }
+        for (year, day) in pages {
+            let url = build_page_url(&base, year, day)?;
+            println!("{year}:{day:02} {url}");
+        }
+        return Ok(());
     }
 
+    let http = build_http(&session.unwrap(), &base, &args.http)?;
+    let mut progress = Progress::new(days.len() + skipped);
+    progress.skipped = skipped;
+    let inputs_saved = download_days(
+        &http,
+        &base,
+        &output,
+        profile,
+        &days,
+        args.jobs.into(),
+        &mut progress,
+    )
+    .await;
+    progress.finish();
+    let pages_saved = download_pages(
+        &http,
+        &base,
+        &output,
+        &pages,
+        args.descriptions,
+        args.examples.as_deref(),
+    )
+    .await?;
+    if !(inputs_saved && pages_saved) {
+        exit(1)
+    }
     Ok(())
 }
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use url::Url;
+
+use crate::{Error, http::Http};
+
+pub use aoc_common::{answers_path, parse_profile, profile_dir};
+
+/// The default profiles file.
+pub const PROFILES_FILE: &str = "profiles.txt";
+/// Overrides [`PROFILES_FILE`].
+pub const PROFILES_VAR: &str = "AOC_PROFILES";
+/// The profile to use when none is given on the command line.
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+
+/// A named account on the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub session: String,
+    /// When the session cookie expires, as copied from the browser. The site
+    /// has no way to ask.
+    pub expires: Option<NaiveDate>,
+}
+
+/// The profiles, one `<name> <session> [<expires>]` line each.
+#[derive(Debug, Default)]
+pub struct Profiles {
+    path: PathBuf,
+    pub profiles: Vec<Profile>,
+}
+
+impl Profiles {
+    /// Load the profiles file, treating a missing file as having no profiles.
+    pub fn load(path: impl AsRef<Path>) -> Result<Profiles, Error> {
+        let path = path.as_ref().to_path_buf();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(format!("{}: {error}", path.display()).into()),
+        };
+
+        let mut profiles = Vec::new();
+        for (i, line) in data.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (name, session, expires) = match fields[..] {
+                [name, session] => (name, session, None),
+                [name, session, expires] => (name, session, Some(expires)),
+                _ => {
+                    return Err(format!(
+                        "{}:{}: expected '<name> <session> [<expires>]'",
+                        path.display(),
+                        i + 1
+                    )
+                    .into());
+                }
+            };
+            let name = parse_profile(name)
+                .map_err(|error| format!("{}:{}: {error}", path.display(), i + 1))?;
+            let expires = expires
+                .map(|expires| NaiveDate::parse_from_str(expires, "%Y-%m-%d"))
+                .transpose()
+                .map_err(|error| {
+                    format!("{}:{}: invalid expiry: {error}", path.display(), i + 1)
+                })?;
+            profiles.push(Profile {
+                name,
+                session: session.to_string(),
+                expires,
+            });
+        }
+        Ok(Profiles { path, profiles })
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Profile, Error> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .ok_or_else(|| {
+                format!("there is no profile '{name}' in {}", self.path.display()).into()
+            })
+    }
+}
+
+/// The session of `profile` if there is one, or else `session`.
+pub fn resolve_session(
+    profiles: &Path,
+    profile: Option<&str>,
+    session: Option<String>,
+) -> Result<Option<String>, Error> {
+    match profile {
+        Some(profile) => Ok(Some(
+            Profiles::load(profiles)?.get(profile)?.session.clone(),
+        )),
+        None => Ok(session),
+    }
+}
+
+/// The logged in user's name from any page of the site, if there is one.
+pub fn parse_user(page: &str) -> Option<String> {
+    let (_, rest) = page.split_once("<div class=\"user\">")?;
+    let name = rest.split('<').next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Check that a session is logged in, returning the user's name.
+pub async fn whoami(http: &Http, base: &Url) -> Result<String, Error> {
+    let response = http.get(base.clone()).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("could not fetch {base}, HTTP {status}").into());
+    }
+    let page = response.text().await?;
+    match parse_user(&page) {
+        Some(user) if !page.contains("/auth/login") => Ok(user),
+        _ => Err("not logged in, the session is invalid or has expired".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_load() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("profiles");
+        std::fs::write(
+            &path,
+            "# name session expires\npersonal abc 2026-12-26\nwork def\n",
+        )
+        .unwrap();
+        let profiles = Profiles::load(&path).unwrap();
+        assert_eq!(
+            profiles.get("personal").unwrap(),
+            &Profile {
+                name: "personal".to_string(),
+                session: "abc".to_string(),
+                expires: NaiveDate::from_ymd_opt(2026, 12, 26),
+            }
+        );
+        assert_eq!(profiles.get("work").unwrap().expires, None);
+        assert!(profiles.get("other").is_err());
+
+        std::fs::write(&path, "personal abc 26/12/2026\n").unwrap();
+        assert!(Profiles::load(&path).is_err());
+        std::fs::write(&path, "../personal abc\n").unwrap();
+        assert!(Profiles::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_parse_user() {
+        let page = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><div class="user">Someone Else <span class="star-count">42*</span></div></div></header>"#;
+        assert_eq!(parse_user(page), Some("Someone Else".to_string()));
+        let page =
+            r#"<div class="user">(anonymous user #1234) <span class="star-count">3*</span></div>"#;
+        assert_eq!(parse_user(page), Some("(anonymous user #1234)".to_string()));
+        assert_eq!(parse_user(r#"<a href="/auth/login">[Log In]</a>"#), None);
+    }
+
+    #[tokio::test]
+    async fn test_whoami() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/")
+            .match_header("cookie", "session=abc")
+            .with_body(
+                r#"<div class="user">Someone Else <span class="star-count">42*</span></div>"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/")
+            .with_body(r#"<a href="/auth/login">[Log In]</a>"#)
+            .create_async()
+            .await;
+
+        let base = Url::parse(&server.url()).unwrap();
+        let http = |session| {
+            let throttle = crate::http::Throttle::new(Duration::ZERO, None);
+            let retry = crate::http::Retry {
+                retries: 0,
+                initial_delay: Duration::ZERO,
+            };
+            Http::new(session, &base, "test", throttle, retry).unwrap()
+        };
+        assert_eq!(whoami(&http("abc"), &base).await.unwrap(), "Someone Else");
+        assert!(whoami(&http("def"), &base).await.is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use std::io::{IsTerminal, Write};
+
+const BAR_WIDTH: usize = 30;
+
+/// Counts of what became of each day of a bulk download, drawn as a progress
+/// bar on the last line of the terminal.
+#[derive(Debug, Default)]
+pub struct Progress {
+    total: usize,
+    pub downloaded: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// Whether to draw the bar, which is only done on a terminal.
+    draw: bool,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Progress {
+        Progress {
+            total,
+            draw: std::io::stderr().is_terminal(),
+            ..Default::default()
+        }
+    }
+
+    pub fn done(&self) -> usize {
+        self.downloaded + self.skipped + self.failed
+    }
+
+    pub fn bar(&self) -> String {
+        let filled = (self.done() * BAR_WIDTH)
+            .checked_div(self.total)
+            .unwrap_or(BAR_WIDTH);
+        format!(
+            "[{}{}] {}/{} {}",
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            self.done(),
+            self.total,
+            self.summary()
+        )
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} downloaded, {} skipped, {} failed",
+            self.downloaded, self.skipped, self.failed
+        )
+    }
+
+    /// Print a message above the bar.
+    pub fn message(&self, message: &str) {
+        if self.draw {
+            eprint!("\r\x1b[K");
+        }
+        eprintln!("{message}");
+        self.redraw();
+    }
+
+    pub fn redraw(&self) {
+        if self.draw {
+            eprint!("\r\x1b[K{}", self.bar());
+            let _ = std::io::stderr().flush();
+        }
+    }
+
+    /// Replace the bar with the final counts.
+    pub fn finish(&self) {
+        if self.draw {
+            eprint!("\r\x1b[K");
+        }
+        eprintln!("{}", self.summary());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar() {
+        let mut progress = Progress::new(6);
+        progress.downloaded = 2;
+        progress.skipped = 1;
+        assert_eq!(
+            progress.bar(),
+            "[###############---------------] 3/6 2 downloaded, 1 skipped, 0 failed"
+        );
+        assert!(
+            Progress::new(0)
+                .bar()
+                .starts_with(&format!("[{}]", "#".repeat(BAR_WIDTH)))
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{parse_days, parse_range};
+
+use crate::{Day, Year};
+
+/// Which puzzles to download: everything, some years, or some days of those years.
+#[derive(Debug, Clone, Default, PartialEq, clap::Args)]
+pub struct Selection {
+    /// Only download these years, either a single year or a range such as 2020..=2024
+    #[arg(long, value_parser = parse_years)]
+    pub year: Option<RangeInclusive<Year>>,
+
+    /// Only download these days, either a single day or a range such as 1..=10
+    #[arg(long, value_parser = parse_days::<Day>)]
+    pub day: Option<RangeInclusive<Day>>,
+}
+
+impl Selection {
+    pub fn matches(&self, year: Year, day: Day) -> bool {
+        self.year.as_ref().is_none_or(|years| years.contains(&year))
+            && self.day.as_ref().is_none_or(|days| days.contains(&day))
+    }
+}
+
+pub fn parse_years(value: &str) -> Result<RangeInclusive<Year>, String> {
+    parse_range(value, crate::calendar::FIRST_YEAR, Year::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_years() {
+        assert_eq!(parse_years("2023.."), Ok(2023..=Year::MAX));
+        assert_eq!(parse_years("..2017"), Ok(2015..=2016));
+    }
+
+    #[test]
+    fn test_matches() {
+        let selection = Selection {
+            year: Some(2024..=2025),
+            day: Some(1..=2),
+        };
+        assert!(selection.matches(2024, 1));
+        assert!(!selection.matches(2024, 3));
+        assert!(!selection.matches(2023, 1));
+        assert!(Selection::default().matches(2015, 25));
+    }
+}
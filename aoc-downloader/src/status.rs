@@ -0,0 +1,253 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Write},
+    path::{Path, PathBuf},
+};
+
+use url::Url;
+
+use crate::{Day, Error, Year, calendar, http::Http, submit::History};
+
+/// Stars per day, from a year's calendar page.
+///
+/// Each day links to its puzzle with a label such as `Day 3, two stars`, or
+/// just `Day 3` before it's been solved.
+pub fn parse_calendar(page: &str) -> BTreeMap<Day, u8> {
+    let mut stars = BTreeMap::new();
+    for (i, label_start) in page.match_indices("aria-label=\"Day ") {
+        let rest = &page[i + label_start.len()..];
+        let Some((label, _)) = rest.split_once('"') else {
+            continue;
+        };
+        let (day, count) = label.split_once(", ").unwrap_or((label, ""));
+        let Ok(day) = day.parse() else {
+            continue;
+        };
+        let count = match count {
+            "one star" => 1,
+            "two stars" => 2,
+            _ => 0,
+        };
+        stars.insert(day, count);
+    }
+    stars
+}
+
+pub async fn fetch_stars(http: &Http, base: &Url, year: Year) -> Result<BTreeMap<Day, u8>, Error> {
+    let response = http.get(base.join(&format!("{year}"))?).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("could not fetch the {year} calendar, HTTP {status}").into());
+    }
+    let page = response.text().await?;
+    // The calendar has a log in link instead of the user's name without a session.
+    if page.contains("/auth/login") {
+        return Err("not logged in, the session has expired".into());
+    }
+    Ok(parse_calendar(&page))
+}
+
+/// The stars on every day fetched so far, one `<year> <day> <stars>` line each.
+#[derive(Debug, Default)]
+pub struct Stars {
+    path: PathBuf,
+    pub days: BTreeMap<(Year, Day), u8>,
+}
+
+impl Stars {
+    /// Load the stars file, treating a missing file as having no stars.
+    pub fn load(path: impl AsRef<Path>) -> Result<Stars, Error> {
+        let path = path.as_ref().to_path_buf();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(format!("{}: {error}", path.display()).into()),
+        };
+
+        let mut days = BTreeMap::new();
+        for (i, line) in data.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parsed = match fields[..] {
+                [year, day, stars] => year
+                    .parse()
+                    .ok()
+                    .zip(day.parse().ok())
+                    .zip(stars.parse().ok()),
+                _ => None,
+            };
+            let Some(((year, day), stars)) = parsed else {
+                return Err(format!(
+                    "{}:{}: expected '<year> <day> <stars>'",
+                    path.display(),
+                    i + 1
+                )
+                .into());
+            };
+            days.insert((year, day), stars);
+        }
+        Ok(Stars { path, days })
+    }
+
+    /// Replace the stars of a year with freshly fetched ones.
+    pub fn update(&mut self, year: Year, stars: BTreeMap<Day, u8>) {
+        self.days.retain(|&(y, _), _| y != year);
+        self.days
+            .extend(stars.into_iter().map(|(day, stars)| ((year, day), stars)));
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let mut data = String::from("# year day stars\n");
+        for ((year, day), stars) in &self.days {
+            writeln!(data, "{year} {day} {stars}")?;
+        }
+        std::fs::write(&self.path, data)
+            .map_err(|error| format!("{}: {error}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+/// A mismatch between the stars earned and the solutions committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    /// Stars were earned, but there's no solution for the day.
+    NoSolution { year: Year, day: Day, stars: u8 },
+    /// There's a solution, but the answer to a part was never submitted.
+    NotSubmitted { year: Year, day: Day, part: u8 },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Finding::NoSolution { year, day, stars } => {
+                let stars = "*".repeat(*stars as usize);
+                write!(f, "{year}:{day:02} has {stars} but no solution")
+            }
+            Finding::NotSubmitted { year, day, part } => write!(
+                f,
+                "{year}:{day:02} has a solution, but part {part} was never submitted"
+            ),
+        }
+    }
+}
+
+/// Compare the stars of the years in `years` with the solutions, which the
+/// caller lists as it's the one that has them, and the submissions log.
+pub fn cross_reference(
+    years: &[Year],
+    stars: &Stars,
+    solutions: &BTreeSet<(Year, Day)>,
+    history: &History,
+) -> Vec<Finding> {
+    let mut findings = vec![];
+    for &year in years {
+        let last_day = calendar::days_in_event(year);
+        for day in 1..=last_day {
+            let count = stars.days.get(&(year, day)).copied().unwrap_or(0);
+            if !solutions.contains(&(year, day)) {
+                if count > 0 {
+                    findings.push(Finding::NoSolution {
+                        year,
+                        day,
+                        stars: count,
+                    });
+                }
+                continue;
+            }
+
+            // The last day's second star comes free with all the others.
+            let parts = if day == last_day { 1 } else { 2 };
+            for part in count + 1..=parts {
+                if history.attempts(year, day, part).next().is_none() {
+                    findings.push(Finding::NotSubmitted { year, day, part });
+                }
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_calendar() {
+        let page = r#"<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2024/day/1" class="calendar-day1 calendar-verycomplete">...</a>
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete">...</a>
+<a aria-label="Day 3" href="/2024/day/3" class="calendar-day3">...</a>
+<span aria-hidden="true" class="calendar-day4">...</span>
+</pre>"#;
+        assert_eq!(
+            parse_calendar(page),
+            BTreeMap::from([(1, 2), (2, 1), (3, 0)])
+        );
+    }
+
+    #[test]
+    fn test_stars_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("stars");
+        let mut stars = Stars::load(&path).unwrap();
+        stars.update(2024, BTreeMap::from([(1, 2), (2, 1)]));
+        stars.update(2023, BTreeMap::from([(1, 1)]));
+        stars.update(2024, BTreeMap::from([(1, 2), (2, 2)]));
+        stars.save().unwrap();
+        let loaded = Stars::load(&path).unwrap();
+        assert_eq!(
+            loaded.days,
+            BTreeMap::from([((2023, 1), 1), ((2024, 1), 2), ((2024, 2), 2)])
+        );
+    }
+
+    #[test]
+    fn test_cross_reference() {
+        let stars = Stars {
+            path: PathBuf::new(),
+            days: BTreeMap::from([
+                ((2024, 1), 2),
+                ((2024, 2), 1),
+                ((2024, 3), 2),
+                ((2024, 25), 0),
+            ]),
+        };
+        let solutions = BTreeSet::from([(2024, 1), (2024, 2), (2024, 4), (2024, 25)]);
+        let temp = tempfile::tempdir().unwrap();
+        let history_path = temp.path().join("status");
+        std::fs::write(
+            &history_path,
+            "2024-12-04T05:10:00+00:00 2024 4 1 too-high 100\n",
+        )
+        .unwrap();
+        let history = History::load(&history_path).unwrap();
+
+        assert_eq!(
+            cross_reference(&[2024], &stars, &solutions, &history),
+            vec![
+                Finding::NotSubmitted {
+                    year: 2024,
+                    day: 2,
+                    part: 2
+                },
+                Finding::NoSolution {
+                    year: 2024,
+                    day: 3,
+                    stars: 2
+                },
+                Finding::NotSubmitted {
+                    year: 2024,
+                    day: 4,
+                    part: 2
+                },
+                Finding::NotSubmitted {
+                    year: 2024,
+                    day: 25,
+                    part: 1
+                },
+            ]
+        );
+    }
+}
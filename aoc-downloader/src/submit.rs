@@ -0,0 +1,390 @@
+use std::{
+    fmt::Display,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use url::Url;
+
+use crate::{Day, Error, Year, http::Http};
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way.
+    Wrong,
+    /// An answer was submitted too recently, and another can be sent after `wait`.
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part has already been solved, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Read the verdict from the page returned after submitting an answer.
+    pub fn parse(page: &str) -> Result<Verdict, Error> {
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Ok(Verdict::TooHigh)
+            } else if text.contains("too low") {
+                Ok(Verdict::TooLow)
+            } else {
+                Ok(Verdict::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(wait, _)| parse_wait(wait))
+                .ok_or("could not find how long to wait before submitting again")?;
+            Ok(Verdict::RateLimited { wait })
+        } else if text.contains("Did you already complete it?") {
+            Ok(Verdict::AlreadySolved)
+        } else {
+            Err(format!("could not understand the response: {}", text.trim()).into())
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+/// The form used in the submissions log.
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited { wait } => write!(f, "rate-limited:{}", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Verdict, Error> {
+        Ok(match s {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "already-solved" => Verdict::AlreadySolved,
+            _ => match s.strip_prefix("rate-limited:") {
+                Some(secs) => Verdict::RateLimited {
+                    wait: Duration::from_secs(secs.parse()?),
+                },
+                None => return Err(format!("unknown verdict '{s}'").into()),
+            },
+        })
+    }
+}
+
+/// The text of the page's `<article>`, which holds the message, without its tags.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(page);
+
+    let mut text = String::new();
+    let mut in_tag = true;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parse a wait such as `35s` or `4m 12s`.
+fn parse_wait(wait: &str) -> Option<Duration> {
+    let mut secs = 0;
+    for part in wait.split_whitespace() {
+        let (number, multiplier) = if let Some(number) = part.strip_suffix('h') {
+            (number, 60 * 60)
+        } else if let Some(number) = part.strip_suffix('m') {
+            (number, 60)
+        } else {
+            (part.strip_suffix('s')?, 1)
+        };
+        secs += number.parse::<u64>().ok()? * multiplier;
+    }
+    Some(Duration::from_secs(secs))
+}
+
+pub async fn submit_answer(
+    http: &Http,
+    base: &Url,
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict, Error> {
+    let url = base.join(&format!("{year}/day/{day}/answer"))?;
+    let form = [("level", part.to_string()), ("answer", answer.to_string())];
+    let page = http
+        .post_form(url, &form)
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Verdict::parse(&page)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub time: DateTime<Utc>,
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Display for Attempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.time.to_rfc3339(),
+            self.year,
+            self.day,
+            self.part,
+            self.verdict,
+            self.answer
+        )
+    }
+}
+
+impl FromStr for Attempt {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Attempt, Error> {
+        let fields: Vec<&str> = line.splitn(6, ' ').collect();
+        let [time, year, day, part, verdict, answer] = fields[..] else {
+            return Err("expected '<time> <year> <day> <part> <verdict> <answer>'".into());
+        };
+        Ok(Attempt {
+            time: DateTime::parse_from_rfc3339(time)?.to_utc(),
+            year: year.parse()?,
+            day: day.parse()?,
+            part: part.parse()?,
+            answer: answer.to_string(),
+            verdict: verdict.parse()?,
+        })
+    }
+}
+
+/// Every answer submitted so far, stored one attempt per line.
+#[derive(Debug, Default)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Load the submissions log, treating a missing file as having no attempts.
+    pub fn load(path: impl AsRef<Path>) -> Result<History, Error> {
+        let path = path.as_ref().to_path_buf();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(format!("{}: {error}", path.display()).into()),
+        };
+
+        let mut attempts = Vec::new();
+        for (i, line) in data.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let attempt = line
+                .parse()
+                .map_err(|error| format!("{}:{}: {error}", path.display(), i + 1))?;
+            attempts.push(attempt);
+        }
+        Ok(History { path, attempts })
+    }
+
+    pub fn attempts(&self, year: Year, day: Day, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| (attempt.year, attempt.day, attempt.part) == (year, day, part))
+    }
+
+    /// Check an answer against earlier attempts before it is submitted.
+    ///
+    /// Fails if the answer is already known to be wrong, and otherwise returns
+    /// warnings, such as the answer being outside the bracket that earlier
+    /// too high and too low answers leave.
+    pub fn check(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<Vec<String>, Error> {
+        let mut warnings = Vec::new();
+        let mut too_high: Option<i128> = None;
+        let mut too_low: Option<i128> = None;
+        for attempt in self.attempts(year, day, part) {
+            if attempt.answer == answer && attempt.verdict.is_wrong() {
+                return Err(format!(
+                    "{answer} was already submitted at {} and was {}",
+                    attempt.time.to_rfc3339(),
+                    attempt.verdict
+                )
+                .into());
+            }
+            if attempt.verdict == Verdict::Correct {
+                warnings.push(format!("{} was already accepted", attempt.answer));
+            }
+
+            let Ok(value) = attempt.answer.parse::<i128>() else {
+                continue;
+            };
+            match attempt.verdict {
+                Verdict::TooHigh => too_high = Some(too_high.map_or(value, |v| v.min(value))),
+                Verdict::TooLow => too_low = Some(too_low.map_or(value, |v| v.max(value))),
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some(too_high) = too_high.filter(|too_high| value >= *too_high) {
+                warnings.push(format!(
+                    "{answer} is not below {too_high}, which was too high"
+                ));
+            }
+            if let Some(too_low) = too_low.filter(|too_low| value <= *too_low) {
+                warnings.push(format!(
+                    "{answer} is not above {too_low}, which was too low"
+                ));
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Add an attempt to the log, creating the file if needed.
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|error| format!("{}: {error}", self.path.display()))?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "# time year day part verdict answer")?;
+        }
+        writeln!(file, "{attempt}")?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        let verdict = |message| Verdict::parse(&page(message)).unwrap();
+        assert_eq!(
+            verdict("That's the right answer!  You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict(
+                "That's not the right answer; your answer is too high.  <a href=\"/2024/day/1\">[Return]</a>"
+            ),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict(
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data."
+            ),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            verdict(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            ),
+            Verdict::RateLimited {
+                wait: Duration::from_secs(65)
+            }
+        );
+        assert_eq!(
+            verdict("You don't seem to be solving the right level.  Did you already complete it?"),
+            Verdict::AlreadySolved
+        );
+        assert!(Verdict::parse(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn test_attempt_round_trip() {
+        let attempt = Attempt {
+            time: DateTime::parse_from_rfc3339("2024-12-01T05:01:02Z")
+                .unwrap()
+                .to_utc(),
+            year: 2024,
+            day: 1,
+            part: 2,
+            answer: "1234".to_string(),
+            verdict: Verdict::RateLimited {
+                wait: Duration::from_secs(30),
+            },
+        };
+        assert_eq!(attempt.to_string().parse::<Attempt>().unwrap(), attempt);
+    }
+
+    #[test]
+    fn test_check() {
+        let attempt = |answer: &str, verdict| Attempt {
+            time: Utc::now(),
+            year: 2024,
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+        };
+        let history = History {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt("100", Verdict::TooHigh),
+                attempt("50", Verdict::TooLow),
+                attempt("80", Verdict::TooHigh),
+            ],
+        };
+        assert!(history.check(2024, 1, 1, "80").is_err());
+        assert!(history.check(2024, 1, 2, "80").unwrap().is_empty());
+        assert!(history.check(2024, 1, 1, "60").unwrap().is_empty());
+        assert_eq!(history.check(2024, 1, 1, "90").unwrap().len(), 1);
+        assert_eq!(history.check(2024, 1, 1, "40").unwrap().len(), 1);
+    }
+}
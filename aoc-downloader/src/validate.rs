@@ -0,0 +1,137 @@
+use std::{fmt::Display, time::Duration};
+
+use reqwest::StatusCode;
+
+/// Why a downloaded input was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    /// The session cookie is missing, invalid or has expired.
+    SessionExpired,
+    /// The puzzle hasn't unlocked yet.
+    Locked,
+    /// Too many requests were made, and the server may have said when to retry.
+    RateLimited { retry_after: Option<Duration> },
+    /// Anything else that isn't a puzzle input, such as a 404 page.
+    Invalid(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::SessionExpired => write!(f, "the session has expired, log in again"),
+            InputError::Locked => write!(f, "the puzzle hasn't unlocked yet"),
+            InputError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "rate limited, retry in {}s", retry_after.as_secs()),
+            InputError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            InputError::Invalid(reason) => write!(f, "not a puzzle input: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl InputError {
+    /// Whether later downloads will fail the same way, so there's no point trying them.
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            InputError::SessionExpired | InputError::RateLimited { .. }
+        )
+    }
+}
+
+const LOG_IN_MESSAGE: &str = "Please log in";
+const LOCKED_MESSAGE: &str = "before it unlocks";
+
+/// Check that a downloaded body is a puzzle input and not an error page.
+pub fn check_input(body: &[u8]) -> Result<(), InputError> {
+    let Ok(text) = std::str::from_utf8(body) else {
+        return Err(InputError::Invalid("not valid UTF-8".to_string()));
+    };
+    if text.trim().is_empty() {
+        return Err(InputError::Invalid("empty".to_string()));
+    }
+    if text.contains(LOG_IN_MESSAGE) {
+        return Err(InputError::SessionExpired);
+    }
+    if text.contains(LOCKED_MESSAGE) {
+        return Err(InputError::Locked);
+    }
+    let start = text.trim_start().to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Err(InputError::Invalid("an HTML page".to_string()));
+    }
+    if start.starts_with("404 not found") {
+        return Err(InputError::Invalid("a 404 page".to_string()));
+    }
+    Ok(())
+}
+
+/// Check the response to an input download.
+pub fn check_download(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    body: &[u8],
+) -> Result<(), InputError> {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(InputError::RateLimited { retry_after });
+    }
+    if !status.is_success() {
+        // The body usually says more than the status does, such as a 400 for
+        // an expired session or a 404 for a locked puzzle.
+        return match check_input(body) {
+            Err(error) => Err(error),
+            Ok(()) => Err(InputError::Invalid(format!("HTTP {status}"))),
+        };
+    }
+    check_input(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_download() {
+        assert_eq!(check_download(StatusCode::OK, None, b"1 2\n3 4\n"), Ok(()));
+        assert_eq!(
+            check_download(
+                StatusCode::BAD_REQUEST,
+                None,
+                b"Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InputError::SessionExpired)
+        );
+        assert_eq!(
+            check_download(
+                StatusCode::NOT_FOUND,
+                None,
+                b"Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+            ),
+            Err(InputError::Locked)
+        );
+        assert_eq!(
+            check_download(
+                StatusCode::TOO_MANY_REQUESTS,
+                Some(Duration::from_secs(60)),
+                b""
+            ),
+            Err(InputError::RateLimited {
+                retry_after: Some(Duration::from_secs(60))
+            })
+        );
+        assert!(matches!(
+            check_download(StatusCode::NOT_FOUND, None, b"404 Not Found"),
+            Err(InputError::Invalid(_))
+        ));
+        assert!(matches!(
+            check_download(StatusCode::OK, None, b"<!DOCTYPE html>\n<html></html>"),
+            Err(InputError::Invalid(_))
+        ));
+        assert!(matches!(
+            check_download(StatusCode::OK, None, b""),
+            Err(InputError::Invalid(_))
+        ));
+    }
+}
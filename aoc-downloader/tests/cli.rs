@@ -0,0 +1,181 @@
+//! Runs the downloader end to end against a local stand-in for the site.
+
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use mockito::{Matcher, Server};
+
+const LOG_IN_PAGE: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+/// Download 2015 days 1 and 2 from `server` into `dir/inputs`.
+fn download(server: &Server, dir: &Path, extra_args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc-downloader"))
+        .arg(dir.join("inputs"))
+        .args(["--year", "2015", "--day", "1..=2", "--min-interval", "0"])
+        .args(extra_args)
+        .env("SESSION", "abc")
+        .env("AOC_BASE_URL", server.url())
+        .env("AOC_CONTACT", "tests@example.com")
+        .env("AOC_THROTTLE_FILE", dir.join("last-request"))
+        .output()
+        .unwrap()
+}
+
+fn input(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("inputs/y2015/day{day:02}"))
+}
+
+#[test]
+fn test_download() {
+    let mut server = Server::new();
+    let user_agent = Matcher::Regex(r"^aoc-downloader/\S+ \(tests@example\.com\)$".to_string());
+    let mocks = [(1, "(()\n"), (2, "2x3x4\n")].map(|(day, body)| {
+        server
+            .mock("GET", format!("/2015/day/{day}/input").as_str())
+            .match_header("cookie", "session=abc")
+            .match_header("user-agent", user_agent.clone())
+            .with_body(body)
+            .create()
+    });
+
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let output = download(&server, dir, &[]);
+    assert!(output.status.success(), "{output:?}");
+    for mock in mocks {
+        mock.assert();
+    }
+    assert_eq!(std::fs::read(input(dir, 1)).unwrap(), b"(()\n");
+    assert_eq!(std::fs::read(input(dir, 2)).unwrap(), b"2x3x4\n");
+}
+
+#[test]
+fn test_skip_existing() {
+    let mut server = Server::new();
+    let existing = server.mock("GET", "/2015/day/1/input").expect(0).create();
+    let missing = server
+        .mock("GET", "/2015/day/2/input")
+        .with_body("2x3x4\n")
+        .create();
+
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    std::fs::create_dir_all(input(dir, 1).parent().unwrap()).unwrap();
+    std::fs::write(input(dir, 1), "saved\n").unwrap();
+    let output = download(&server, dir, &[]);
+    assert!(output.status.success(), "{output:?}");
+    existing.assert();
+    missing.assert();
+    assert_eq!(std::fs::read(input(dir, 1)).unwrap(), b"saved\n");
+
+    // Unless they're forced.
+    let existing = server
+        .mock("GET", "/2015/day/1/input")
+        .with_body("(()\n")
+        .create();
+    let output = download(&server, dir, &["--force"]);
+    assert!(output.status.success(), "{output:?}");
+    existing.assert();
+    assert_eq!(std::fs::read(input(dir, 1)).unwrap(), b"(()\n");
+}
+
+#[test]
+fn test_auth_failure() {
+    let mut server = Server::new();
+    let rejected = server
+        .mock("GET", "/2015/day/1/input")
+        .with_status(400)
+        .with_body(LOG_IN_PAGE)
+        .create();
+    // An expired session stops the run, rather than failing every day.
+    let skipped = server.mock("GET", "/2015/day/2/input").expect(0).create();
+
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let output = download(&server, dir, &[]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    rejected.assert();
+    skipped.assert();
+    assert!(!input(dir, 1).exists());
+    let quarantined = dir.join("inputs/quarantine/y2015/day01");
+    assert_eq!(std::fs::read_to_string(quarantined).unwrap(), LOG_IN_PAGE);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the session has expired"));
+}
+
+#[test]
+fn test_rate_limited() {
+    let mut server = Server::new();
+    // Retried once, after waiting as long as the server asks.
+    let limited = server
+        .mock("GET", "/2015/day/1/input")
+        .with_status(429)
+        .with_header("retry-after", "1")
+        .expect(2)
+        .create();
+    let skipped = server.mock("GET", "/2015/day/2/input").expect(0).create();
+
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let output = download(&server, dir, &["--retries", "1"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    limited.assert();
+    skipped.assert();
+    assert!(!input(dir, 1).exists());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("rate limited, retry in 1s"));
+
+    // A retry that succeeds saves the input as usual.
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    server.reset();
+    let limited = server
+        .mock("GET", "/2015/day/1/input")
+        .with_status(429)
+        .expect(1)
+        .create();
+    let retried = server
+        .mock("GET", "/2015/day/1/input")
+        .with_body("(()\n")
+        .create();
+    server
+        .mock("GET", "/2015/day/2/input")
+        .with_body("2x3x4\n")
+        .create();
+    let output = download(&server, dir, &["--retries", "1"]);
+    assert!(output.status.success(), "{output:?}");
+    limited.assert();
+    retried.assert();
+    assert_eq!(std::fs::read(input(dir, 1)).unwrap(), b"(()\n");
+}
+
+#[test]
+fn test_profile() {
+    let mut server = Server::new();
+    let mocks = [1, 2].map(|day| {
+        server
+            .mock("GET", format!("/2015/day/{day}/input").as_str())
+            .match_header("cookie", "session=def")
+            .with_body("1\n")
+            .create()
+    });
+
+    // The profile's session is used instead of SESSION, and its inputs are
+    // kept apart.
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let profiles = dir.join("profiles.txt");
+    std::fs::write(&profiles, "personal abc\nwork def 2026-12-26\n").unwrap();
+    let profiles = profiles.to_str().unwrap();
+    let output = download(&server, dir, &["--profile", "work", "--profiles", profiles]);
+    assert!(output.status.success(), "{output:?}");
+    for mock in mocks {
+        mock.assert();
+    }
+    assert!(dir.join("inputs/work/y2015/day01").exists());
+    assert!(!input(dir, 1).exists());
+
+    let output = download(&server, dir, &["--profile", "home", "--profiles", profiles]);
+    assert!(!output.status.success());
+}
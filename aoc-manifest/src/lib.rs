@@ -0,0 +1,234 @@
+//! A record of each downloaded input, to tell when one has been truncated or
+//! edited since.
+//!
+//! Some parsers stop at the first empty line or the last newline, so a
+//! damaged input can give a wrong answer rather than an error.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+    io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// The manifest's name in an inputs directory.
+pub const MANIFEST_FILE: &str = "manifest.txt";
+
+pub fn manifest_path(dir: &Path) -> PathBuf {
+    dir.join(MANIFEST_FILE)
+}
+
+pub fn sha256(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// An input as it was downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: usize,
+    pub day: usize,
+    pub profile: Option<String>,
+    pub bytes: usize,
+    pub sha256: String,
+    pub downloaded: DateTime<Utc>,
+}
+
+impl Entry {
+    pub fn new(
+        year: usize,
+        day: usize,
+        profile: Option<&str>,
+        data: &[u8],
+        downloaded: DateTime<Utc>,
+    ) -> Entry {
+        Entry {
+            year,
+            day,
+            profile: profile.map(str::to_string),
+            bytes: data.len(),
+            sha256: sha256(data),
+            downloaded,
+        }
+    }
+
+    fn parse(line: &str) -> Option<Entry> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [year, day, profile, bytes, sha256, downloaded] = fields[..] else {
+            return None;
+        };
+        Some(Entry {
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            profile: (profile != "-").then(|| profile.to_string()),
+            bytes: bytes.parse().ok()?,
+            sha256: sha256.to_string(),
+            downloaded: DateTime::parse_from_rfc3339(downloaded).ok()?.to_utc(),
+        })
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.year,
+            self.day,
+            self.profile.as_deref().unwrap_or("-"),
+            self.bytes,
+            self.sha256,
+            self.downloaded.to_rfc3339()
+        )
+    }
+}
+
+/// Something wrong with an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Shorter than when it was downloaded.
+    Truncated { expected: usize, actual: usize },
+    /// Changed since it was downloaded.
+    Edited,
+    /// Downloaded inputs always end with a newline.
+    NoTrailingNewline,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Truncated { expected, actual } => write!(
+                f,
+                "the input is truncated, it has {actual} bytes but was downloaded with {expected}"
+            ),
+            Problem::Edited => write!(f, "the input has been edited since it was downloaded"),
+            Problem::NoTrailingNewline => write!(f, "the input doesn't end with a newline"),
+        }
+    }
+}
+
+/// The entries of an inputs directory, one line each.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    path: PathBuf,
+    entries: BTreeMap<(usize, usize), Entry>,
+}
+
+impl Manifest {
+    /// Load the manifest, treating a missing file as having no entries.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Manifest> {
+        let path = path.as_ref().to_path_buf();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+
+        let mut entries = BTreeMap::new();
+        for (i, line) in data.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = Entry::parse(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{}:{}: expected '<year> <day> <profile> <bytes> <sha256> <downloaded>'",
+                        path.display(),
+                        i + 1
+                    ),
+                )
+            })?;
+            entries.insert((entry.year, entry.day), entry);
+        }
+        Ok(Manifest { path, entries })
+    }
+
+    pub fn get(&self, year: usize, day: usize) -> Option<&Entry> {
+        self.entries.get(&(year, day))
+    }
+
+    pub fn insert(&mut self, entry: Entry) {
+        self.entries.insert((entry.year, entry.day), entry);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut data = String::from("# year day profile bytes sha256 downloaded\n");
+        for entry in self.entries.values() {
+            let _ = writeln!(data, "{entry}");
+        }
+        std::fs::write(&self.path, data)
+    }
+}
+
+/// Check an input against its entry, if it has one.
+pub fn check(entry: Option<&Entry>, data: &[u8]) -> Vec<Problem> {
+    let mut problems = vec![];
+    if let Some(entry) = entry {
+        if data.len() < entry.bytes {
+            problems.push(Problem::Truncated {
+                expected: entry.bytes,
+                actual: data.len(),
+            });
+        } else if sha256(data) != entry.sha256 {
+            problems.push(Problem::Edited);
+        }
+    }
+    if !data.is_empty() && !data.ends_with(b"\n") {
+        problems.push(Problem::NoTrailingNewline);
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("manifest");
+        let downloaded = DateTime::parse_from_rfc3339("2024-12-01T05:00:02Z")
+            .unwrap()
+            .to_utc();
+        let mut manifest = Manifest::load(&path).unwrap();
+        manifest.insert(Entry::new(2024, 1, None, b"3   4\n", downloaded));
+        manifest.insert(Entry::new(2024, 2, Some("work"), b"7 6\n", downloaded));
+        manifest.save().unwrap();
+
+        let loaded = Manifest::load(&path).unwrap();
+        assert_eq!(loaded.entries, manifest.entries);
+        assert_eq!(
+            loaded.get(2024, 2).unwrap().to_string(),
+            "2024 2 work 4 cf06cdf1cdcda2f8e4b9a07b87a73de9b3e7c2dcce21589aac32225670abc33a 2024-12-01T05:00:02+00:00"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let entry = Entry::new(2024, 9, None, b"2333133121414131402\n", Utc::now());
+        assert_eq!(check(Some(&entry), b"2333133121414131402\n"), vec![]);
+        assert_eq!(
+            check(Some(&entry), b"23331331214141"),
+            vec![
+                Problem::Truncated {
+                    expected: 20,
+                    actual: 14
+                },
+                Problem::NoTrailingNewline
+            ]
+        );
+        assert_eq!(
+            check(Some(&entry), b"2333133121414131403\n"),
+            vec![Problem::Edited]
+        );
+        assert_eq!(check(None, b"1\n2"), vec![Problem::NoTrailingNewline]);
+    }
+}
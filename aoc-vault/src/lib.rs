@@ -0,0 +1,214 @@
+//! Puzzle inputs and answers, encrypted so they can be committed.
+//!
+//! Advent of Code asks for inputs not to be published, so they are kept in
+//! the vault encrypted with [age](https://age-encryption.org), using either an
+//! identity file made by `age-keygen` or a passphrase.
+//!
+//! Prefer an identity file. The vault is meant to be public, so a passphrase
+//! is all that stands between anyone and the inputs, and it has to hold up to
+//! offline guessing. Passphrases use age's full scrypt work factor, which
+//! takes around a second for each file decrypted.
+
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use age::{scrypt, secrecy::SecretString, x25519};
+
+/// The vault, next to the crates in the workspace.
+pub const VAULT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../vault");
+
+/// Overrides [`VAULT_DIR`].
+pub const VAULT_DIR_VAR: &str = "AOC_VAULT_DIR";
+/// The path of an age identity file to use as the key.
+pub const KEY_FILE_VAR: &str = "AOC_KEY_FILE";
+/// A passphrase to use as the key, if there's no key file. It should be a
+/// strong one, see the crate docs.
+pub const PASSPHRASE_VAR: &str = "AOC_PASSPHRASE";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The file is only in the vault, and no key was given to decrypt it.
+    NoKey(PathBuf),
+    /// The key file couldn't be read or doesn't hold an identity.
+    Key(PathBuf, String),
+    Encrypt(String),
+    Decrypt(PathBuf, String),
+    Io(PathBuf, io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoKey(path) => write!(
+                f,
+                "{} is encrypted, set {KEY_FILE_VAR} or {PASSPHRASE_VAR} to decrypt it",
+                path.display()
+            ),
+            Error::Key(path, error) => write!(f, "invalid key file {}: {error}", path.display()),
+            Error::Encrypt(error) => write!(f, "could not encrypt: {error}"),
+            Error::Decrypt(path, error) => {
+                write!(f, "could not decrypt {}: {error}", path.display())
+            }
+            Error::Io(path, error) => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The vault directory, from [`VAULT_DIR_VAR`] if it's set.
+pub fn vault_dir() -> PathBuf {
+    std::env::var_os(VAULT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(VAULT_DIR))
+}
+
+/// Inputs are laid out as in the inputs directory, with an `.age` extension.
+pub fn input_path(vault: &Path, year: usize, day: usize) -> PathBuf {
+    vault
+        .join(format!("y{year}"))
+        .join(format!("day{day:02}.age"))
+}
+
+pub fn answers_path(vault: &Path) -> PathBuf {
+    vault.join("answers.txt.age")
+}
+
+pub enum Key {
+    Identity(x25519::Identity),
+    Passphrase(SecretString),
+}
+
+impl Key {
+    /// The first identity in an age identity file.
+    pub fn from_file(path: &Path) -> Result<Key> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|error| Error::Io(path.to_path_buf(), error))?;
+        let line = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .ok_or_else(|| Error::Key(path.to_path_buf(), "there is no identity".to_string()))?;
+        let identity = x25519::Identity::from_str(line)
+            .map_err(|error| Error::Key(path.to_path_buf(), error.to_string()))?;
+        Ok(Key::Identity(identity))
+    }
+
+    /// The key from [`KEY_FILE_VAR`] or [`PASSPHRASE_VAR`], if either is set.
+    pub fn from_env() -> Result<Option<Key>> {
+        if let Some(path) = std::env::var_os(KEY_FILE_VAR) {
+            return Key::from_file(Path::new(&path)).map(Some);
+        }
+        Ok(std::env::var(PASSPHRASE_VAR)
+            .ok()
+            .map(|passphrase| Key::Passphrase(SecretString::from(passphrase))))
+    }
+
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let encrypted = match self {
+            Key::Identity(identity) => age::encrypt(&identity.to_public(), data),
+            Key::Passphrase(passphrase) => {
+                age::encrypt(&scrypt::Recipient::new(passphrase.clone()), data)
+            }
+        };
+        encrypted.map_err(|error| Error::Encrypt(error.to_string()))
+    }
+
+    /// Decrypt `data`, which was read from `path`.
+    pub fn decrypt(&self, path: &Path, data: &[u8]) -> Result<Vec<u8>> {
+        let decrypted = match self {
+            Key::Identity(identity) => age::decrypt(identity, data),
+            Key::Passphrase(passphrase) => {
+                age::decrypt(&scrypt::Identity::new(passphrase.clone()), data)
+            }
+        };
+        decrypted.map_err(|error| Error::Decrypt(path.to_path_buf(), error.to_string()))
+    }
+}
+
+/// Read and decrypt a file from the vault.
+pub fn read(path: &Path, key: Option<&Key>) -> Result<Vec<u8>> {
+    let data = std::fs::read(path).map_err(|error| Error::Io(path.to_path_buf(), error))?;
+    let key = key.ok_or_else(|| Error::NoKey(path.to_path_buf()))?;
+    key.decrypt(path, &data)
+}
+
+/// Encrypt `data` into the vault, unless the file there already holds the
+/// same data. Encrypting is randomised, so this keeps unchanged files from
+/// changing. Returns whether the file was written.
+pub fn write(path: &Path, key: &Key, data: &[u8]) -> Result<bool> {
+    if let Ok(existing) = std::fs::read(path)
+        && key
+            .decrypt(path, &existing)
+            .is_ok_and(|existing| existing == data)
+    {
+        return Ok(false);
+    }
+
+    let encrypted = key.encrypt(data)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| Error::Io(parent.to_path_buf(), error))?;
+    }
+    std::fs::write(path, encrypted).map_err(|error| Error::Io(path.to_path_buf(), error))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret;
+
+    #[test]
+    fn test_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let vault = temp.path();
+        let path = input_path(vault, 2024, 1);
+        let key = Key::Identity(x25519::Identity::generate());
+        assert!(write(&path, &key, b"3   4\n").unwrap());
+        let encrypted = std::fs::read(&path).unwrap();
+        assert_ne!(encrypted, b"3   4\n");
+        assert_eq!(read(&path, Some(&key)).unwrap(), b"3   4\n");
+
+        // Writing the same data again leaves the file alone.
+        assert!(!write(&path, &key, b"3   4\n").unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), encrypted);
+        assert!(write(&path, &key, b"4   3\n").unwrap());
+
+        // Each passphrase operation takes around a second, so only do a couple.
+        let passphrase = Key::Passphrase(SecretString::from("hunter2".to_string()));
+        let encrypted = passphrase.encrypt(b"5   6\n").unwrap();
+        assert_eq!(passphrase.decrypt(&path, &encrypted).unwrap(), b"5   6\n");
+
+        assert!(matches!(read(&path, None), Err(Error::NoKey(_))));
+        let wrong = Key::Identity(x25519::Identity::generate());
+        assert!(matches!(read(&path, Some(&wrong)), Err(Error::Decrypt(..))));
+    }
+
+    #[test]
+    fn test_key_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("vault-key");
+        let identity = x25519::Identity::generate();
+        std::fs::write(
+            &path,
+            format!(
+                "# created: today\n# public key: {}\n{}\n",
+                identity.to_public(),
+                identity.to_string().expose_secret()
+            ),
+        )
+        .unwrap();
+        let key = Key::from_file(&path).unwrap();
+        let encrypted = age::encrypt(&identity.to_public(), b"data").unwrap();
+        assert_eq!(key.decrypt(&path, &encrypted).unwrap(), b"data");
+
+        std::fs::write(&path, "# nothing here\n").unwrap();
+        assert!(matches!(Key::from_file(&path), Err(Error::Key(..))));
+    }
+}
@@ -0,0 +1,68 @@
+//! Generates a test for every example under `examples/`, which
+//! `tests/examples.rs` includes. Examples marked for review are ignored.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+use aoc_common::REVIEW_PREFIX;
+
+/// Parse the number out of a directory name such as `y2024` or `day16`.
+fn number(name: &str, prefix: &str) -> Option<usize> {
+    name.strip_prefix(prefix)?.parse().ok()
+}
+
+/// Whether an example's answers file marks it as needing review.
+fn needs_review(answers_path: &Path) -> bool {
+    fs::read_to_string(answers_path)
+        .is_ok_and(|answers| answers.lines().any(|line| line.starts_with(REVIEW_PREFIX)))
+}
+
+fn sorted_entries(dir: &Path) -> Vec<fs::DirEntry> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    entries
+}
+
+fn main() {
+    let examples_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let mut tests = String::new();
+    for year_entry in sorted_entries(&examples_dir) {
+        let Some(year) = number(&year_entry.file_name().to_string_lossy(), "y") else {
+            continue;
+        };
+        for day_entry in sorted_entries(&year_entry.path()) {
+            let Some(day) = number(&day_entry.file_name().to_string_lossy(), "day") else {
+                continue;
+            };
+            for example_entry in sorted_entries(&day_entry.path()) {
+                let path = example_entry.path();
+                if path.extension().is_none_or(|extension| extension != "txt") {
+                    continue;
+                }
+                let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+                let test_name: String = name
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect();
+                let ignore = if needs_review(&path.with_extension("answers")) {
+                    "#[ignore = \"needs review\"]\n"
+                } else {
+                    ""
+                };
+                writeln!(
+                    tests,
+                    "#[test]\n{}fn y{:04}_day{:02}_{}() {{\n    check({}, {}, {:?});\n}}\n",
+                    ignore, year, day, test_name, year, day, name
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out_path, tests).unwrap();
+}
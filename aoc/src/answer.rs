@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use num_bigint::{BigInt, BigUint};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i64),
+    BigNumber(BigInt),
+    Text(String),
+    /// The part has no answer, such as part 2 of the final day.
+    Empty,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::BigNumber(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Empty => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    i64::try_from(value)
+                        .map(Answer::Number)
+                        .unwrap_or_else(|_| Answer::BigNumber(BigInt::from(value)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        i64::try_from(&value)
+            .map(Answer::Number)
+            .unwrap_or(Answer::BigNumber(value))
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(value: BigUint) -> Self {
+        BigInt::from(value).into()
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_integer() {
+        assert_eq!(Answer::from(42u8), Answer::Number(42));
+        assert_eq!(Answer::from(-3i16), Answer::Number(-3));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::BigNumber(BigInt::from(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn test_from_big_number() {
+        assert_eq!(Answer::from(BigUint::from(7u32)), Answer::Number(7));
+        assert_eq!(
+            Answer::from(BigUint::from(u64::MAX)).to_string(),
+            "18446744073709551615"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+pub use aoc_common::{answers_path, ANSWERS_FILE};
+
+use crate::{error::ResultExt, Error, Result, Vault};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PartKey {
+    pub year: usize,
+    pub day: usize,
+    pub part: u8,
+}
+
+impl Display for PartKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "y{:04} d{:02} part {}", self.year, self.day, self.part)
+    }
+}
+
+/// Known answers, stored one per line as `<year> <day> <part> <answer>`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<PartKey, String>,
+}
+
+impl Answers {
+    /// Load the answers file, falling back to the one in `vault`, and
+    /// treating a missing file as having no answers.
+    pub fn load(path: impl AsRef<Path>, vault: &Vault) -> Result<Answers> {
+        let path = path.as_ref();
+        match std::fs::File::open(path) {
+            Ok(mut file) => Answers::parse(&mut file),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                let vault_path = aoc_vault::answers_path(&vault.dir);
+                if !vault_path.exists() {
+                    return Ok(Answers::default());
+                }
+                let data = vault
+                    .read(&vault_path)
+                    .map_err(|error| Error::input(path, error))?;
+                Answers::parse(&mut &data[..])
+            }
+            Err(error) => Err(Error::input(path, error)),
+        }
+    }
+
+    pub fn parse(buf: &mut dyn Read) -> Result<Answers> {
+        let reader = BufReader::new(buf);
+        let mut answers = BTreeMap::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, ' ');
+            let (Some(year), Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(Error::parse("expected '<year> <day> <part> <answer>'").at_line(i + 1));
+            };
+            let key = PartKey {
+                year: year.parse().at_line(i + 1)?,
+                day: day.parse().at_line(i + 1)?,
+                part: part.parse().at_line(i + 1)?,
+            };
+            answers.insert(key, answer.to_string());
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, key: &PartKey) -> Option<&str> {
+        self.answers.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: PartKey, answer: impl Display) {
+        self.answers.insert(key, answer.to_string());
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# year day part answer")?;
+        for (key, answer) in &self.answers {
+            writeln!(f, "{} {} {} {}", key.year, key.day, key.part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let data = b"# year day part answer\n2024 1 1 11\n2024 17 1 4,6,3,5\n\n2024 25 2 -\n";
+        let answers = Answers::parse(&mut &data[..]).unwrap();
+        let key = PartKey {
+            year: 2024,
+            day: 17,
+            part: 1,
+        };
+        assert_eq!(answers.get(&key), Some("4,6,3,5"));
+        assert_eq!(
+            Answers::parse(&mut answers.to_string().as_bytes()).unwrap(),
+            answers
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Answers::parse(&mut &b"2024 1 1 11\n2024 x 1 3\n"[..]).unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+}
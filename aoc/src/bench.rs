@@ -0,0 +1,167 @@
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{answers::PartKey, Error, Result};
+
+/// Timings from repeated runs of one part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        if runs == 0 {
+            return Stats {
+                runs,
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                stddev: Duration::ZERO,
+            };
+        }
+
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// The part number that timings of the parse step are saved under.
+pub const PARSE_PART: u8 = 0;
+
+/// Run `f` `warmup` times without timing it, then time `runs` more runs.
+pub fn bench<T>(mut f: impl FnMut() -> Result<T>, warmup: usize, runs: usize) -> Result<Stats> {
+    for _ in 0..warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&samples))
+}
+
+/// A saved benchmark result, with times in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    pub year: usize,
+    pub day: usize,
+    pub part: u8,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl BenchRecord {
+    pub fn new(key: PartKey, stats: &Stats) -> BenchRecord {
+        BenchRecord {
+            year: key.year,
+            day: key.day,
+            part: key.part,
+            runs: stats.runs,
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            mean_ns: stats.mean.as_nanos() as u64,
+            stddev_ns: stats.stddev.as_nanos() as u64,
+        }
+    }
+
+    pub fn key(&self) -> PartKey {
+        PartKey {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    /// How much slower `stats` is than this record, as a percentage of its
+    /// median. `None` if this record's median is zero, so there's nothing to
+    /// compare against.
+    pub fn change(&self, stats: &Stats) -> Option<f64> {
+        let median = self.median();
+        (!median.is_zero())
+            .then(|| (stats.median.as_secs_f64() / median.as_secs_f64() - 1.0) * 100.0)
+    }
+}
+
+pub fn load_records(path: impl AsRef<Path>) -> Result<Vec<BenchRecord>> {
+    let path = path.as_ref();
+    let data = std::fs::read(path).map_err(|error| Error::input(path, error))?;
+    serde_json::from_slice(&data).map_err(|error| Error::parse(error.to_string()))
+}
+
+pub fn save_records(path: impl AsRef<Path>, records: &[BenchRecord]) -> Result<()> {
+    let data =
+        serde_json::to_vec_pretty(records).map_err(|error| Error::parse(error.to_string()))?;
+    std::fs::write(path, data)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<_> = [4, 1, 3, 2].map(Duration::from_millis).into();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn test_change() {
+        let key = PartKey {
+            year: 2024,
+            day: 1,
+            part: 1,
+        };
+        let stats = Stats::from_samples(&[Duration::from_millis(3)]);
+        let baseline = BenchRecord::new(key, &Stats::from_samples(&[Duration::from_millis(2)]));
+        assert_eq!(baseline.change(&stats), Some(50.0));
+
+        let empty = BenchRecord::new(key, &Stats::from_samples(&[]));
+        assert_eq!(empty.change(&stats), None);
+    }
+}
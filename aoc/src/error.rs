@@ -0,0 +1,208 @@
+use std::{
+    fmt::Display,
+    io,
+    num::{ParseFloatError, ParseIntError},
+    path::PathBuf,
+    string::FromUtf8Error,
+};
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// The input for a day could not be opened.
+    Input {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Io(io::Error),
+    /// The input did not match the expected puzzle format.
+    Parse(String),
+    /// The input was well formed, but no answer could be found from it.
+    NoSolution(String),
+    /// A part other than 1 or 2 was asked for.
+    InvalidPart(u8),
+}
+
+/// An error from a solver, along with where it happened.
+///
+/// Solvers only know the position in the input, so the year, day and part are
+/// filled in by the runner.
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub year: Option<usize>,
+    pub day: Option<usize>,
+    pub part: Option<u8>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new(kind: ErrorKind) -> Error {
+        Error {
+            kind,
+            year: None,
+            day: None,
+            part: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn parse(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::Parse(message.into()))
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::NoSolution(message.into()))
+    }
+
+    pub fn input(path: impl Into<PathBuf>, source: io::Error) -> Error {
+        Error::new(ErrorKind::Input {
+            path: path.into(),
+            source,
+        })
+    }
+
+    /// Set the (1-based) line of the input the error was found on.
+    pub fn at_line(mut self, line: usize) -> Error {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    /// Set the (1-based) line and column of the input the error was found at.
+    pub fn at(mut self, line: usize, column: usize) -> Error {
+        self.column.get_or_insert(column);
+        self.at_line(line)
+    }
+
+    pub fn in_day(mut self, year: usize, day: usize) -> Error {
+        self.year.get_or_insert(year);
+        self.day.get_or_insert(day);
+        self
+    }
+
+    pub fn in_part(mut self, year: usize, day: usize, part: u8) -> Error {
+        self.part.get_or_insert(part);
+        self.in_day(year, day)
+    }
+
+    /// Whether this error is because the input file doesn't exist yet.
+    pub fn is_missing_input(&self) -> bool {
+        matches!(&self.kind, ErrorKind::Input { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let (Some(year), Some(day)) = (self.year, self.day) {
+            write!(f, "y{:04} d{:02}", year, day)?;
+            if let Some(part) = self.part {
+                write!(f, " part {}", part)?;
+            }
+            write!(f, ": ")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "line {}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ", column {}", column)?;
+            }
+            write!(f, ": ")?;
+        }
+
+        match &self.kind {
+            ErrorKind::Input { path, source } => {
+                write!(f, "could not open input {}: {}", path.display(), source)
+            }
+            ErrorKind::Io(error) => write!(f, "could not read input: {}", error),
+            ErrorKind::Parse(message) => write!(f, "invalid input: {}", message),
+            ErrorKind::NoSolution(message) => write!(f, "no solution: {}", message),
+            ErrorKind::InvalidPart(part) => write!(f, "there is no part {}", part),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Input { source, .. } => Some(source),
+            ErrorKind::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::new(ErrorKind::Io(value))
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(value: ParseIntError) -> Self {
+        Error::parse(value.to_string())
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(value: ParseFloatError) -> Self {
+        Error::parse(value.to_string())
+    }
+}
+
+impl From<num_bigint::ParseBigIntError> for Error {
+    fn from(value: num_bigint::ParseBigIntError) -> Self {
+        Error::parse(value.to_string())
+    }
+}
+
+impl From<FromUtf8Error> for Error {
+    fn from(value: FromUtf8Error) -> Self {
+        Error::parse(value.to_string())
+    }
+}
+
+/// Attach input positions to errors while parsing.
+pub trait ResultExt<T> {
+    fn at_line(self, line: usize) -> Result<T>;
+    fn at(self, line: usize, column: usize) -> Result<T>;
+}
+
+impl<T, E> ResultExt<T> for std::result::Result<T, E>
+where
+    E: Into<Error>,
+{
+    fn at_line(self, line: usize) -> Result<T> {
+        self.map_err(|error| error.into().at_line(line))
+    }
+
+    fn at(self, line: usize, column: usize) -> Result<T> {
+        self.map_err(|error| error.into().at(line, column))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = Error::parse("unexpected 'x'").at(3, 5).in_part(2024, 16, 1);
+        assert_eq!(
+            error.to_string(),
+            "y2024 d16 part 1: line 3, column 5: invalid input: unexpected 'x'"
+        );
+
+        let error = Error::no_solution("no route to the end");
+        assert_eq!(error.to_string(), "no solution: no route to the end");
+    }
+
+    #[test]
+    fn test_innermost_position_wins() {
+        let result: std::result::Result<(), _> = "x".parse::<u8>().map(|_| ());
+        let error = result.at(2, 4).at_line(9).unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, Some(4));
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+pub use aoc_common::REVIEW_PREFIX;
+
+use crate::{error::ResultExt, Aoc, Error, Result};
+
+/// The directory holding puzzle examples, next to the crates in the workspace.
+pub const EXAMPLES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../examples");
+
+/// The directory holding the examples of one day, laid out as `y{year}/day{day}`.
+pub fn examples_dir(root: &Path, year: usize, day: usize) -> PathBuf {
+    root.join(format!("y{:04}", year))
+        .join(format!("day{:02}", day))
+}
+
+/// A puzzle example, stored as `<name>.txt` with its expected answers in `<name>.answers`.
+///
+/// The answers file has one `<part> <answer>` line for each part the example
+/// covers, since plenty of examples only apply to one of the parts. Lines
+/// starting with [`REVIEW_PREFIX`] mark it as needing review.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: Vec<u8>,
+    pub expected: BTreeMap<u8, String>,
+    /// Why the example needs reviewing, if it does.
+    pub review: Vec<String>,
+}
+
+impl Example {
+    /// Load the example whose input is at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Example> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let input = std::fs::read(path).map_err(|error| Error::input(path, error))?;
+
+        let answers_path = path.with_extension("answers");
+        let mut answers_file = std::fs::File::open(&answers_path)
+            .map_err(|error| Error::input(&answers_path, error))?;
+        let (expected, review) = parse_answers(&mut answers_file)?;
+
+        Ok(Example {
+            name,
+            input,
+            expected,
+            review,
+        })
+    }
+
+    /// Run the example through a solution, returning a description of each
+    /// part that didn't give the expected answer.
+    pub fn check(&self, solution: &Aoc) -> Vec<String> {
+        let parsed = match solution.parse(&mut &self.input[..]) {
+            Ok(parsed) => parsed,
+            Err(error) => return vec![format!("{}: {}", self.name, error)],
+        };
+
+        let mut failures = Vec::new();
+        for (&part, expected) in &self.expected {
+            match solution.solve(part, &parsed) {
+                Ok(answer) if answer.to_string() == *expected => {}
+                Ok(answer) => failures.push(format!(
+                    "{} part {}: got {}, expected {}",
+                    self.name, part, answer, expected
+                )),
+                Err(error) => failures.push(format!("{} part {}: {}", self.name, part, error)),
+            }
+        }
+        failures
+    }
+}
+
+/// Parse an answers file into the expected answers and the reasons for review.
+fn parse_answers(buf: &mut dyn Read) -> Result<(BTreeMap<u8, String>, Vec<String>)> {
+    let reader = BufReader::new(buf);
+    let mut expected = BTreeMap::new();
+    let mut review = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if let Some(reason) = line.strip_prefix(REVIEW_PREFIX) {
+            review.push(reason.trim().to_string());
+            continue;
+        }
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (part, answer) = line
+            .split_once(' ')
+            .ok_or_else(|| Error::parse("expected '<part> <answer>'").at_line(i + 1))?;
+        let part: u8 = part.parse().at_line(i + 1)?;
+        if !Aoc::PARTS.contains(&part) {
+            return Err(Error::parse(format!("there is no part {}", part)).at_line(i + 1));
+        }
+        expected.insert(part, answer.to_string());
+    }
+    Ok((expected, review))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let (expected, review) =
+            parse_answers(&mut &b"# part answer\n1 7036\n\n2 a b\n"[..]).unwrap();
+        assert_eq!(
+            expected,
+            BTreeMap::from([(1, "7036".to_string()), (2, "a b".to_string())])
+        );
+        assert!(review.is_empty());
+        let (_, review) = parse_answers(&mut &b"# review: unsure\n1 7036\n"[..]).unwrap();
+        assert_eq!(review, vec!["unsure".to_string()]);
+        let error = parse_answers(&mut &b"1 7036\n3 45\n"[..]).unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use aoc_common::profile_dir;
+use aoc_manifest::{Manifest, Problem};
+use aoc_vault::Key;
+
+use crate::{Aoc, Error, Result};
+
+/// Where solvers read their puzzle input from.
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    /// A directory of inputs, laid out as `y{year}/day{day:02}`, with each
+    /// profile's inputs in a directory of their own. Inputs that aren't there
+    /// are decrypted from the vault.
+    Dir {
+        dir: PathBuf,
+        profile: Option<String>,
+        vault: Vault,
+    },
+    /// A single file, used whatever the day.
+    File(PathBuf),
+    /// Input already in memory, such as from stdin.
+    Data(Vec<u8>),
+}
+
+impl InputSource {
+    /// Read all of stdin, so that it can be given to each part.
+    pub fn stdin() -> io::Result<InputSource> {
+        let mut data = Vec::new();
+        io::stdin().read_to_end(&mut data)?;
+        Ok(InputSource::Data(data))
+    }
+
+    pub fn open(&self, solution: &Aoc) -> Result<Box<dyn Read + '_>> {
+        let path = match self {
+            InputSource::Dir {
+                dir,
+                profile,
+                vault,
+            } => {
+                let profile = profile.as_deref();
+                return open_dir(&profile_dir(dir, profile), profile, vault, solution);
+            }
+            InputSource::File(path) => path.clone(),
+            InputSource::Data(data) => return Ok(Box::new(&data[..])),
+        };
+        let file = File::open(&path).map_err(|error| Error::input(path, error))?;
+        Ok(Box::new(file))
+    }
+
+    /// Check an input read from here against the manifest it was downloaded
+    /// with. Only a directory of inputs has one.
+    pub fn check(&self, solution: &Aoc, data: &[u8]) -> Result<Vec<Problem>> {
+        let InputSource::Dir { dir, profile, .. } = self else {
+            return Ok(Vec::new());
+        };
+        let path = aoc_manifest::manifest_path(&profile_dir(dir, profile.as_deref()));
+        let manifest = Manifest::load(&path).map_err(|error| Error::input(path, error))?;
+        let entry = manifest.get(solution.year, solution.day);
+        Ok(aoc_manifest::check(entry, data))
+    }
+}
+
+/// The vault to decrypt inputs and answers from, along with the key to
+/// decrypt them with.
+#[derive(Clone)]
+pub struct Vault {
+    pub dir: PathBuf,
+    pub key: Option<Arc<Key>>,
+}
+
+impl Vault {
+    pub fn new(dir: PathBuf, key: Option<Key>) -> Vault {
+        Vault {
+            dir,
+            key: key.map(Arc::new),
+        }
+    }
+
+    /// The vault and key given by the environment, see [`aoc_vault`].
+    pub fn from_env() -> aoc_vault::Result<Vault> {
+        Ok(Vault::new(aoc_vault::vault_dir(), Key::from_env()?))
+    }
+
+    /// The vault holding a profile's inputs.
+    pub fn profile(&self, profile: Option<&str>) -> Vault {
+        Vault {
+            dir: profile_dir(&self.dir, profile),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        aoc_vault::read(path, self.key.as_deref()).map_err(io::Error::other)
+    }
+}
+
+impl Debug for Vault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Vault")
+            .field("dir", &self.dir)
+            .field("key", &self.key.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Open an input from a profile's directory, falling back to decrypting it
+/// from the profile's vault, and then to downloading it.
+fn open_dir(
+    dir: &Path,
+    profile: Option<&str>,
+    vault: &Vault,
+    solution: &Aoc,
+) -> Result<Box<dyn Read>> {
+    let path = dir
+        .join(format!("y{}", solution.year))
+        .join(format!("day{:02}", solution.day));
+    let error = match File::open(&path) {
+        Ok(file) => return Ok(Box::new(file)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => error,
+        Err(error) => return Err(Error::input(path, error)),
+    };
+
+    let vault = vault.profile(profile);
+    let vault_path = aoc_vault::input_path(&vault.dir, solution.year, solution.day);
+    if vault_path.exists() {
+        let data = vault
+            .read(&vault_path)
+            .map_err(|error| Error::input(path, error))?;
+        return Ok(Box::new(io::Cursor::new(data)));
+    }
+    #[cfg(feature = "download")]
+    if let Some(file) = download(dir, profile, solution)? {
+        return Ok(Box::new(file));
+    }
+    Err(Error::input(path, error))
+}
+
+/// Download a missing input into `dir`, if there's a session to download it
+/// with, either the profile's or `SESSION`.
+#[cfg(feature = "download")]
+fn download(dir: &Path, profile: Option<&str>, solution: &Aoc) -> Result<Option<File>> {
+    use aoc_downloader::Downloader;
+
+    let input = |error: aoc_downloader::Error| {
+        let path = dir
+            .join(format!("y{}", solution.year))
+            .join(format!("day{:02}", solution.day));
+        Error::input(path, io::Error::other(error.to_string()))
+    };
+    let Some(downloader) = Downloader::from_env(profile).map_err(input)? else {
+        return Ok(None);
+    };
+    let year = solution
+        .year
+        .try_into()
+        .map_err(|_| input("invalid year".into()))?;
+    let day = solution
+        .day
+        .try_into()
+        .map_err(|_| input("invalid day".into()))?;
+    let path = downloader.download_input(dir, year, day).map_err(input)?;
+    let file = File::open(&path).map_err(|error| Error::input(path, error))?;
+    Ok(Some(file))
+}
@@ -1,30 +1,128 @@
 // #![feature(iterator_try_collect)]
 // #![feature(iter_intersperse)]
-use std::io;
+use std::{any::Any, io};
 
-type Solver = fn(&mut dyn io::Read);
+mod answer;
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod examples;
+mod input;
+pub mod scaffold;
+pub mod selection;
+
+pub use answer::Answer;
+pub use error::{Error, ErrorKind, Result};
+pub use input::{InputSource, Vault};
+
+/// A part that reads the input itself.
+pub type Solver = fn(&mut dyn io::Read) -> Result<Answer>;
+type ParseFn = fn(&mut dyn io::Read) -> Result<Parsed>;
+type PartFn = fn(&Parsed) -> Result<Answer>;
+
+/// The parsed input of a day, shared by both parts.
+pub struct Parsed(Box<dyn Any>);
+
+impl Parsed {
+    pub fn new<T: Any>(value: T) -> Parsed {
+        Parsed(Box::new(value))
+    }
+
+    /// Panics if `T` isn't the type that was parsed, which `parsed_aoc!` rules out.
+    pub fn get<T: Any>(&self) -> &T {
+        self.0
+            .downcast_ref()
+            .expect("parsed input has the type returned by parse")
+    }
+
+    /// Like [`Parsed::get`], taking the type from the parse function that made it.
+    pub fn get_from<T: Any>(&self, _parse: fn(&mut dyn io::Read) -> Result<T>) -> &T {
+        self.get()
+    }
+}
+
+enum Stages {
+    /// Each part reads the input itself.
+    Read([Solver; 2]),
+    /// The input is parsed once and shared by both parts.
+    Parsed(ParseFn, [PartFn; 2]),
+}
 
 pub struct Aoc {
     pub year: usize,
     pub day: usize,
-    pub part1: Solver,
-    pub part2: Solver,
+    stages: Stages,
+}
+
+/// Build an [`Aoc`] whose parts share one parsed input.
+///
+/// `parse` reads the input into any type, and `part1` and `part2` each take a
+/// reference to it.
+macro_rules! parsed_aoc {
+    ($year:expr, $day:expr, $parse:expr, $part1:expr, $part2:expr $(,)?) => {
+        $crate::Aoc::parsed(
+            $year,
+            $day,
+            |input| $parse(input).map($crate::Parsed::new),
+            |parsed| {
+                let input = parsed.get_from($parse);
+                $part1(input)
+            },
+            |parsed| {
+                let input = parsed.get_from($parse);
+                $part2(input)
+            },
+        )
+    };
 }
 
 impl Aoc {
+    pub const PARTS: [u8; 2] = [1, 2];
+
     const fn new(year: usize, day: usize, part1: Solver, part2: Solver) -> Aoc {
         Aoc {
             year,
             day,
-            part1,
-            part2,
+            stages: Stages::Read([part1, part2]),
+        }
+    }
+
+    const fn parsed(year: usize, day: usize, parse: ParseFn, part1: PartFn, part2: PartFn) -> Aoc {
+        Aoc {
+            year,
+            day,
+            stages: Stages::Parsed(parse, [part1, part2]),
+        }
+    }
+
+    pub fn get_input<'a>(&self, source: &'a InputSource) -> Result<Box<dyn io::Read + 'a>> {
+        source.open(self)
+    }
+
+    /// Parse the input, ready to be given to either part.
+    ///
+    /// Solutions whose parts read the input themselves just read it into memory.
+    pub fn parse(&self, input: &mut dyn io::Read) -> Result<Parsed> {
+        match self.stages {
+            Stages::Read(_) => {
+                let mut data = Vec::new();
+                input.read_to_end(&mut data)?;
+                Ok(Parsed::new(data))
+            }
+            Stages::Parsed(parse, _) => parse(input),
         }
     }
 
-    pub fn get_input(&self) -> Box<dyn io::Read> {
-        let file =
-            std::fs::File::open(format!("inputs/y{}/day{:02}", self.year, self.day)).unwrap();
-        Box::new(file)
+    /// Solve part 1 or 2 from the result of [`Aoc::parse`].
+    pub fn solve(&self, part: u8, parsed: &Parsed) -> Result<Answer> {
+        if !Aoc::PARTS.contains(&part) {
+            return Err(Error::new(ErrorKind::InvalidPart(part)));
+        }
+        let index = usize::from(part) - 1;
+        match &self.stages {
+            Stages::Read(solvers) => solvers[index](&mut &parsed.get::<Vec<u8>>()[..]),
+            Stages::Parsed(_, parts) => parts[index](parsed),
+        }
     }
 }
 
@@ -33,3 +131,115 @@ inventory::collect!(Aoc);
 pub mod y2023;
 pub mod y2024;
 pub mod y2025;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(buf: &mut dyn io::Read) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        buf.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    fn part1(data: &[u8]) -> Result<Answer> {
+        Ok(data.len().into())
+    }
+
+    fn part2(data: &[u8]) -> Result<Answer> {
+        Ok(String::from_utf8(data.to_vec())?.into())
+    }
+
+    #[test]
+    fn test_parse_once() {
+        let aoc = parsed_aoc!(2000, 1, parse, part1, part2);
+        let parsed = aoc.parse(&mut &b"abc"[..]).unwrap();
+        assert_eq!(aoc.solve(1, &parsed).unwrap(), Answer::Number(3));
+        assert_eq!(aoc.solve(2, &parsed).unwrap(), Answer::Text("abc".into()));
+        for part in [0, 3] {
+            let error = aoc.solve(part, &parsed).unwrap_err();
+            assert!(matches!(error.kind, ErrorKind::InvalidPart(p) if p == part));
+        }
+    }
+
+    #[test]
+    fn test_vault_input() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let vault_dir = dir.join("vault");
+        let key = aoc_vault::Key::Identity(age::x25519::Identity::generate());
+        aoc_vault::write(&aoc_vault::input_path(&vault_dir, 2000, 1), &key, b"abc").unwrap();
+        let work = vault_dir.join("work");
+        aoc_vault::write(&aoc_vault::input_path(&work, 2000, 1), &key, b"de").unwrap();
+        let vault = Vault::new(vault_dir.clone(), Some(key));
+
+        // Inputs that aren't in the inputs directory are decrypted from the vault.
+        let aoc = parsed_aoc!(2000, 1, parse, part1, part2);
+        let source = InputSource::Dir {
+            dir: dir.join("inputs"),
+            profile: None,
+            vault: vault.clone(),
+        };
+        let parsed = aoc.parse(&mut aoc.get_input(&source).unwrap()).unwrap();
+        assert_eq!(aoc.solve(2, &parsed).unwrap(), Answer::Text("abc".into()));
+
+        // A profile's inputs are in a vault of their own.
+        let source = InputSource::Dir {
+            dir: dir.join("inputs"),
+            profile: Some("work".to_string()),
+            vault,
+        };
+        let parsed = aoc.parse(&mut aoc.get_input(&source).unwrap()).unwrap();
+        assert_eq!(aoc.solve(1, &parsed).unwrap(), Answer::Number(2));
+
+        let source = InputSource::Dir {
+            dir: dir.join("inputs"),
+            profile: Some("work".to_string()),
+            vault: Vault::new(vault_dir, None),
+        };
+        let error = aoc.get_input(&source).err().unwrap();
+        assert!(error.to_string().contains("is encrypted"));
+        assert!(!error.is_missing_input());
+
+        let aoc = parsed_aoc!(2000, 2, parse, part1, part2);
+        assert!(aoc.get_input(&source).err().unwrap().is_missing_input());
+    }
+
+    #[test]
+    fn test_check_input() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let work = dir.join("work");
+        std::fs::create_dir_all(&work).unwrap();
+        let sha256 = aoc_manifest::sha256(b"1 2\n");
+        std::fs::write(
+            aoc_manifest::manifest_path(&work),
+            format!("2000 1 work 4 {sha256} 2024-12-01T05:00:02+00:00\n"),
+        )
+        .unwrap();
+
+        let aoc = parsed_aoc!(2000, 1, parse, part1, part2);
+        let source = InputSource::Dir {
+            dir: dir.to_path_buf(),
+            profile: Some("work".to_string()),
+            vault: Vault::new(dir.join("vault"), None),
+        };
+        assert_eq!(source.check(&aoc, b"1 2\n").unwrap(), vec![]);
+        assert_eq!(
+            source.check(&aoc, b"1 3\n").unwrap(),
+            vec![aoc_manifest::Problem::Edited]
+        );
+        assert_eq!(
+            source.check(&aoc, b"1").unwrap(),
+            vec![
+                aoc_manifest::Problem::Truncated {
+                    expected: 4,
+                    actual: 1
+                },
+                aoc_manifest::Problem::NoTrailingNewline
+            ]
+        );
+        // Only inputs from a directory are checked.
+        assert_eq!(InputSource::Data(vec![]).check(&aoc, b"1").unwrap(), vec![]);
+    }
+}
@@ -1,36 +1,745 @@
-use std::{process::exit, time::Instant};
+use std::{
+    fmt::Display,
+    io::Read,
+    path::PathBuf,
+    process::exit,
+    time::{Duration, Instant},
+};
 
-use advent_of_code::Aoc;
-use clap::Parser;
+use advent_of_code::{
+    answers::{answers_path, Answers, PartKey},
+    bench::{self, BenchRecord, Stats},
+    scaffold,
+    selection::Selection,
+    Answer, Aoc, InputSource, Parsed, Result, Vault,
+};
+use clap::{Parser, Subcommand};
 
+/// Run Advent of Code solutions
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
-    year: usize,
-    day: usize,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Only run this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Read the input from this file instead of the inputs directory, or from stdin with '-'
+    #[arg(long, short)]
+    input: Option<PathBuf>,
+
+    /// The directory holding puzzle inputs, laid out as y{year}/day{day}
+    #[arg(long, env = "AOC_INPUT_DIR", default_value = "inputs", global = true)]
+    input_dir: PathBuf,
+
+    /// Use this profile's inputs, from the directory of that name in the
+    /// inputs directory, and its own answers file
+    #[arg(long, env = "AOC_PROFILE", global = true, value_parser = aoc_common::parse_profile)]
+    profile: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run solutions and check the answers against the answers file
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Record answers for parts that don't have a known answer yet
+        #[arg(long)]
+        save: bool,
+    },
+    /// Time solutions over repeated runs, with the input held in memory
+    Bench(BenchArgs),
+    /// List the registered solutions, one `<year> <day>` line each
+    List {
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// Wait for the next puzzle to unlock, download its input and run its
+    /// solution, with the session from SESSION or the profile
+    #[cfg(feature = "download")]
+    Wait {
+        /// How many seconds to keep retrying for after the unlock
+        #[arg(long, default_value_t = 60)]
+        grace: u64,
+    },
+    /// Fetch the stars earned in each year, and compare them with the
+    /// solutions, with the session from SESSION or the profile
+    #[cfg(feature = "download")]
+    Status(StatusArgs),
+    /// Add a new day from the template, creating the year's module if needed
+    New {
+        year: usize,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The source directory of the solutions crate
+        #[arg(long, default_value = scaffold::SRC_DIR)]
+        src_dir: PathBuf,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Only benchmark this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// How many timed runs to make of each part
+    #[arg(
+        long,
+        short = 'n',
+        default_value_t = 10,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    runs: usize,
+
+    /// How many untimed runs to make before timing starts
+    #[arg(long, default_value_t = 2)]
+    warmup: usize,
+
+    /// Save the results as JSON to this file
+    #[arg(long)]
+    save: Option<PathBuf>,
+
+    /// Compare the results against ones saved with --save
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// How much slower than the baseline median, in percent, counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[cfg(feature = "download")]
+#[derive(Debug, clap::Args)]
+struct StatusArgs {
+    /// Only fetch these years, either a single year or a range such as 2020..=2024
+    #[arg(long, value_parser = aoc_downloader::selection::parse_years)]
+    year: Option<std::ops::RangeInclusive<aoc_common::Year>>,
+
+    /// Where the stars of each day are recorded
+    #[arg(long, default_value = "stars.txt")]
+    stars: PathBuf,
+
+    /// The submissions log, to tell which answers were never submitted
+    #[arg(long, default_value = "submissions.txt")]
+    log: PathBuf,
+}
+
+type PartResult = (u8, Result<(Answer, Duration)>);
+
+struct DayResult {
+    parse: Result<Duration>,
+    parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    fn failed(&self) -> bool {
+        self.parse.is_err() || self.parts.iter().any(|(_, result)| result.is_err())
+    }
+}
+
+/// Warn about an input that isn't as it was downloaded, since that can give a
+/// wrong answer rather than an error.
+fn check_input(solution: &Aoc, source: &InputSource, data: &[u8]) {
+    match source.check(solution, data) {
+        Ok(problems) => {
+            for problem in problems {
+                eprintln!(
+                    "warning: y{:04} d{:02}: {}",
+                    solution.year, solution.day, problem
+                );
+            }
+        }
+        Err(error) => eprintln!("warning: {}", error),
+    }
+}
+
+fn parse_input(solution: &Aoc, source: &InputSource) -> Result<(Parsed, Duration)> {
+    let data = read_input(solution, source)?;
+    check_input(solution, source, &data);
+    let start = Instant::now();
+    let parsed = solution.parse(&mut &data[..])?;
+    Ok((parsed, start.elapsed()))
+}
+
+fn run(solution: &Aoc, source: &InputSource, parts: &[u8]) -> DayResult {
+    eprintln!("y{:04} d{:02}", solution.year, solution.day);
+    let (parsed, parse_time) = match parse_input(solution, source) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            let error = error.in_day(solution.year, solution.day);
+            eprintln!("error: {}", error);
+            return DayResult {
+                parse: Err(error),
+                parts: Vec::new(),
+            };
+        }
+    };
+    eprintln!("(parsed in {:?})", parse_time);
+
+    let mut results = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let result = solution
+            .solve(part, &parsed)
+            .map(|answer| (answer, start.elapsed()))
+            .map_err(|e| e.in_part(solution.year, solution.day, part));
+        match &result {
+            Ok((answer, time)) => {
+                println!("Part {}: {}", part, answer);
+                eprintln!("(solved in {:?})", time);
+            }
+            Err(error) => eprintln!("error: {}", error),
+        }
+        results.push((part, result));
+    }
+    DayResult {
+        parse: Ok(parse_time),
+        parts: results,
+    }
+}
+
+fn print_table(rows: &[Vec<String>]) {
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..column_count)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
+
+fn print_summary(parts: &[u8], results: &[(&Aoc, DayResult)]) {
+    let mut header = vec!["Day".to_string(), "Parse".to_string()];
+    for part in parts {
+        header.push(format!("Part {}", part));
+        header.push("Time".to_string());
+    }
+    let mut rows = vec![header];
+    for (solution, result) in results {
+        let mut row = vec![format!("y{:04} d{:02}", solution.year, solution.day)];
+        match result.parse {
+            Ok(time) => row.push(format!("{:.2?}", time)),
+            Err(_) => row.push("error".to_string()),
+        }
+        for (_, result) in &result.parts {
+            match result {
+                Ok((answer, time)) => {
+                    row.push(answer.to_string());
+                    row.push(format!("{:.2?}", time));
+                }
+                Err(_) => {
+                    row.push("error".to_string());
+                    row.push(String::new());
+                }
+            }
+        }
+        rows.push(row);
+    }
+
+    println!();
+    print_table(&rows);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// The answer matches the known answer.
+    Pass,
+    /// The answer differs from the known answer, or the solver failed.
+    Fail,
+    /// There is no known answer to compare against.
+    New,
+    /// There is no input to run the solver on.
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::New => "new",
+            Status::Missing => "missing",
+        };
+        f.pad(status)
+    }
+}
+
+fn verify_part(
+    solution: &Aoc,
+    part: u8,
+    parsed: &Result<Parsed>,
+    answers: &Answers,
+) -> (Status, String) {
+    let key = PartKey {
+        year: solution.year,
+        day: solution.day,
+        part,
+    };
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) if error.is_missing_input() => return (Status::Missing, String::new()),
+        Err(error) => return (Status::Fail, error.to_string()),
+    };
+    let answer = match solution.solve(part, parsed) {
+        Ok(answer) => answer.to_string(),
+        Err(error) => return (Status::Fail, error.to_string()),
+    };
+
+    match answers.get(&key) {
+        Some(expected) if expected == answer => (Status::Pass, answer),
+        Some(expected) => (Status::Fail, format!("{} (expected {})", answer, expected)),
+        None => (Status::New, answer),
+    }
+}
+
+/// Returns whether every known answer was reproduced.
+fn verify(selection: &Selection, input_dir: PathBuf, profile: Option<String>, save: bool) -> bool {
+    let vault = vault_from_env();
+    let answers_path = answers_path(&input_dir, profile.as_deref());
+    let mut answers = match Answers::load(&answers_path, &vault.profile(profile.as_deref())) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("error: {}: {}", answers_path.display(), error);
+            return false;
+        }
+    };
+    let source = InputSource::Dir {
+        dir: input_dir,
+        profile,
+        vault,
+    };
+
+    let mut counts = [0; 4];
+    for solution in selection.solutions() {
+        let parsed = parse_input(solution, &source).map(|(parsed, _)| parsed);
+        for part in Aoc::PARTS {
+            let (status, detail) = verify_part(solution, part, &parsed, &answers);
+            println!(
+                "y{:04} d{:02} part {}  {:<7}  {}",
+                solution.year, solution.day, part, status, detail
+            );
+
+            counts[status as usize] += 1;
+            if status == Status::New && save {
+                let key = PartKey {
+                    year: solution.year,
+                    day: solution.day,
+                    part,
+                };
+                answers.insert(key, detail);
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} new, {} missing",
+        counts[Status::Pass as usize],
+        counts[Status::Fail as usize],
+        counts[Status::New as usize],
+        counts[Status::Missing as usize],
+    );
+
+    if save && counts[Status::New as usize] > 0 {
+        if let Err(error) = answers.save(&answers_path) {
+            eprintln!(
+                "error: could not save {}: {}",
+                answers_path.display(),
+                error
+            );
+            return false;
+        }
+        eprintln!(
+            "Saved {} new answers to {}",
+            counts[Status::New as usize],
+            answers_path.display()
+        );
+    }
+
+    counts[Status::Fail as usize] == 0
+}
+
+fn read_input(solution: &Aoc, source: &InputSource) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    solution.get_input(source)?.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// A row of the bench table, comparing against the baseline if there is one.
+///
+/// Clears `success` if this is a regression.
+fn bench_row(
+    key: PartKey,
+    stats: &Stats,
+    args: &BenchArgs,
+    baseline: Option<&[BenchRecord]>,
+    success: &mut bool,
+) -> Vec<String> {
+    let part = if key.part == bench::PARSE_PART {
+        "parse".to_string()
+    } else {
+        key.part.to_string()
+    };
+    let mut row = vec![
+        format!("y{:04} d{:02}", key.year, key.day),
+        part,
+        stats.runs.to_string(),
+        format!("{:.2?}", stats.min),
+        format!("{:.2?}", stats.median),
+        format!("{:.2?}", stats.mean),
+        format!("{:.2?}", stats.stddev),
+    ];
+    if let Some(baseline) = baseline {
+        match baseline.iter().find(|record| record.key() == key) {
+            Some(record) => {
+                row.push(format!("{:.2?}", record.median()));
+                match record.change(stats) {
+                    Some(change) if change > args.threshold => {
+                        row.push(format!("{:+.1}% REGRESSION", change));
+                        *success = false;
+                    }
+                    Some(change) => row.push(format!("{:+.1}%", change)),
+                    None => row.push("-".to_string()),
+                }
+            }
+            None => {
+                row.push("-".to_string());
+                row.push("new".to_string());
+            }
+        }
+    }
+    row
+}
+
+/// Returns whether every part ran without errors or regressions.
+fn bench(args: &BenchArgs, source: &InputSource) -> bool {
+    let baseline = match &args.baseline {
+        Some(path) => match bench::load_records(path) {
+            Ok(records) => Some(records),
+            Err(error) => {
+                eprintln!("error: {}: {}", path.display(), error);
+                return false;
+            }
+        },
+        None => None,
+    };
+
+    let mut header: Vec<String> = ["Day", "Part", "Runs", "Min", "Median", "Mean", "Std dev"]
+        .map(String::from)
+        .into();
+    if baseline.is_some() {
+        header.push("Baseline".to_string());
+        header.push("Change".to_string());
+    }
+    let mut rows = vec![header];
+    let mut records = Vec::new();
+    let mut success = true;
+
+    for solution in args.selection.solutions() {
+        let input = match read_input(solution, source) {
+            Ok(input) => input,
+            Err(error) if error.is_missing_input() => continue,
+            Err(error) => {
+                eprintln!("error: {}", error.in_day(solution.year, solution.day));
+                success = false;
+                continue;
+            }
+        };
+        check_input(solution, source, &input);
+
+        let mut key = PartKey {
+            year: solution.year,
+            day: solution.day,
+            part: bench::PARSE_PART,
+        };
+        eprintln!("Benchmarking y{:04} d{:02} parse", key.year, key.day);
+        let parse = || solution.parse(&mut &input[..]);
+        let stats = match bench::bench(parse, args.warmup, args.runs) {
+            Ok(stats) => stats,
+            Err(error) => {
+                eprintln!("error: {}", error.in_day(key.year, key.day));
+                success = false;
+                continue;
+            }
+        };
+        rows.push(bench_row(
+            key,
+            &stats,
+            args,
+            baseline.as_deref(),
+            &mut success,
+        ));
+        records.push(BenchRecord::new(key, &stats));
+
+        let Ok(parsed) = parse() else { continue };
+        for part in Aoc::PARTS {
+            if args.part.is_some_and(|only_part| only_part != part) {
+                continue;
+            }
+            key.part = part;
+            eprintln!("Benchmarking {}", key);
+
+            let solve = || solution.solve(part, &parsed);
+            let stats = match bench::bench(solve, args.warmup, args.runs) {
+                Ok(stats) => stats,
+                Err(error) => {
+                    eprintln!("error: {}", error.in_part(key.year, key.day, part));
+                    success = false;
+                    continue;
+                }
+            };
+            rows.push(bench_row(
+                key,
+                &stats,
+                args,
+                baseline.as_deref(),
+                &mut success,
+            ));
+            records.push(BenchRecord::new(key, &stats));
+        }
+    }
+
+    println!();
+    print_table(&rows);
+
+    if let Some(path) = &args.save {
+        if let Err(error) = bench::save_records(path, &records) {
+            eprintln!("error: could not save {}: {}", path.display(), error);
+            return false;
+        }
+        eprintln!("Saved {} results to {}", records.len(), path.display());
+    }
+
+    success
+}
+
+/// A downloader with the session from the environment or the profile, or
+/// `None` after reporting why there isn't one.
+#[cfg(feature = "download")]
+fn downloader_from_env(profile: Option<&str>) -> Option<aoc_downloader::Downloader> {
+    match aoc_downloader::Downloader::from_env(profile) {
+        Ok(Some(downloader)) => Some(downloader),
+        Ok(None) => {
+            eprintln!("error: SESSION or --profile is required to download inputs");
+            None
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            None
+        }
+    }
+}
+
+/// Returns whether the next puzzle's input was downloaded and its solution
+/// ran without errors.
+#[cfg(feature = "download")]
+fn wait(input_dir: PathBuf, profile: Option<String>, grace: Duration) -> bool {
+    let Some(downloader) = downloader_from_env(profile.as_deref()) else {
+        return false;
+    };
+    let dir = aoc_common::profile_dir(&input_dir, profile.as_deref());
+    let (year, day) = match downloader.wait_for_unlock(&dir, grace) {
+        Ok((year, day, true)) => (year as usize, day as usize),
+        Ok(_) => return false,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return false;
+        }
+    };
+
+    let selection = Selection {
+        year: Some(year),
+        days: Some(day..=day),
+    };
+    let Some(solution) = selection.solutions().into_iter().next() else {
+        eprintln!("Could not find solutions for {}", selection);
+        return false;
+    };
+    let source = InputSource::Dir {
+        dir: input_dir,
+        profile,
+        vault: vault_from_env(),
+    };
+    !run(solution, &source, &Aoc::PARTS).failed()
+}
+
+/// Print the stars of the selected years, and where they don't match the
+/// registered solutions or the submissions log.
+#[cfg(feature = "download")]
+fn report_status(
+    downloader: &aoc_downloader::Downloader,
+    args: &StatusArgs,
+) -> std::result::Result<(), aoc_downloader::Error> {
+    use aoc_downloader::{
+        calendar,
+        clock::{Clock, SystemClock},
+        status::{self, Stars},
+        submit::History,
+    };
+
+    let years: Vec<_> = (calendar::FIRST_YEAR..=calendar::latest_event(SystemClock.now()))
+        .filter(|year| args.year.as_ref().is_none_or(|years| years.contains(year)))
+        .collect();
+    let mut stars = Stars::load(&args.stars)?;
+    for &year in &years {
+        let fetched = downloader.fetch_stars(year)?;
+        let total: u32 = fetched.values().map(|&count| u32::from(count)).sum();
+        println!("{}: {} stars", year, total);
+        stars.update(year, fetched);
+    }
+    stars.save()?;
+
+    let solutions = Selection::default()
+        .solutions()
+        .into_iter()
+        .map(|solution| {
+            (
+                solution.year as aoc_common::Year,
+                solution.day as aoc_common::Day,
+            )
+        })
+        .collect();
+    let history = History::load(&args.log)?;
+    for finding in status::cross_reference(&years, &stars, &solutions, &history) {
+        println!("{}", finding);
+    }
+    Ok(())
+}
+
+/// The vault and key given by the environment, exiting if the key is invalid.
+fn vault_from_env() -> Vault {
+    Vault::from_env().unwrap_or_else(|error| {
+        eprintln!("error: {}", error);
+        exit(1)
+    })
 }
 
 fn main() {
     let args = Args::parse();
 
-    for solution in inventory::iter::<Aoc> {
-        if solution.year == args.year && solution.day == args.day {
-            eprintln!("y{:04} d{:02}", solution.year, solution.day);
-            let mut input = solution.get_input();
-            let part1_start = Instant::now();
-            (solution.part1)(&mut input);
-            let part1_time = part1_start.elapsed();
-            eprintln!("(solved in {:?})", part1_time);
-
-            let mut input = solution.get_input();
-            let part2_start = Instant::now();
-            (solution.part2)(&mut input);
-            let part2_time = part2_start.elapsed();
-
-            eprintln!("(solved in {:?})", part2_time);
+    match args.command {
+        Some(Command::Verify { selection, save }) => {
+            if !verify(&selection, args.input_dir, args.profile, save) {
+                exit(1)
+            }
+            return;
+        }
+        Some(Command::Bench(bench_args)) => {
+            let source = InputSource::Dir {
+                dir: args.input_dir,
+                profile: args.profile,
+                vault: vault_from_env(),
+            };
+            if !bench(&bench_args, &source) {
+                exit(1)
+            }
+            return;
+        }
+        Some(Command::List { selection }) => {
+            for solution in selection.solutions() {
+                println!("{} {}", solution.year, solution.day);
+            }
+            return;
+        }
+        #[cfg(feature = "download")]
+        Some(Command::Wait { grace }) => {
+            if !wait(args.input_dir, args.profile, Duration::from_secs(grace)) {
+                exit(1)
+            }
+            return;
+        }
+        #[cfg(feature = "download")]
+        Some(Command::Status(status_args)) => {
+            let Some(downloader) = downloader_from_env(args.profile.as_deref()) else {
+                exit(1)
+            };
+            if let Err(error) = report_status(&downloader, &status_args) {
+                eprintln!("error: {}", error);
+                exit(1)
+            }
+            return;
+        }
+        Some(Command::New { year, day, src_dir }) => {
+            match scaffold::new_day(&src_dir, year, day.into()) {
+                Ok(paths) => {
+                    for path in paths {
+                        eprintln!("Wrote {}", path.display());
+                    }
+                }
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    exit(1)
+                }
+            }
             return;
         }
+        None => {}
+    }
+
+    let solutions = args.selection.solutions();
+    if solutions.is_empty() {
+        eprintln!("Could not find solutions for {}", args.selection);
+        exit(1)
+    }
+
+    let source = match args.input {
+        Some(_) if solutions.len() > 1 => {
+            eprintln!("--input can only be used when running a single day");
+            exit(1)
+        }
+        Some(path) if path.as_os_str() == "-" => match InputSource::stdin() {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("error: could not read stdin: {}", error);
+                exit(1)
+            }
+        },
+        Some(path) => InputSource::File(path),
+        None => InputSource::Dir {
+            dir: args.input_dir,
+            profile: args.profile,
+            vault: vault_from_env(),
+        },
+    };
+
+    let parts: Vec<u8> = Aoc::PARTS
+        .into_iter()
+        .filter(|part| args.part.is_none_or(|only_part| only_part == *part))
+        .collect();
+    let results: Vec<_> = solutions
+        .into_iter()
+        .map(|solution| (solution, run(solution, &source, &parts)))
+        .collect();
+    if results.len() > 1 {
+        print_summary(&parts, &results);
     }
 
-    eprintln!("Could not find solution for y{} d{}", args.year, args.day);
-    exit(1)
+    if results.iter().any(|(_, result)| result.failed()) {
+        exit(1)
+    }
 }
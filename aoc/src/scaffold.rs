@@ -0,0 +1,176 @@
+use std::{
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// The `src` directory of this crate, which new days are added to.
+pub const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+/// The source of a new day, from `templates/day.rs`.
+pub fn render(year: usize, day: usize) -> String {
+    TEMPLATE
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string())
+}
+
+/// Insert `declaration` among the lines matching `siblings`, keeping them sorted
+/// by the name captured by `siblings`.
+///
+/// If there are no siblings the declaration goes at the end.
+fn insert_sorted(contents: &str, declaration: &str, name: &str, siblings: &Regex) -> String {
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let mut index = lines.len();
+    let mut last_sibling = None;
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(captures) = siblings.captures(line) {
+            if &captures[1] > name {
+                index = i;
+                break;
+            }
+            last_sibling = Some(i);
+        }
+    }
+    if index == lines.len() {
+        if let Some(last_sibling) = last_sibling {
+            index = last_sibling + 1;
+        }
+    }
+
+    lines.insert(index, declaration);
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    contents
+}
+
+/// Add the path to an error, which `std::fs` leaves out.
+fn at_path(path: &Path) -> impl FnOnce(io::Error) -> io::Error + '_ {
+    move |error| io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+}
+
+fn add_module(path: &Path, declaration: &str, name: &str, siblings: &Regex) -> io::Result<()> {
+    let contents = fs::read_to_string(path).map_err(at_path(path))?;
+    let contents = insert_sorted(&contents, declaration, name, siblings);
+    fs::write(path, contents).map_err(at_path(path))
+}
+
+/// Create `y{year}/day{day}.rs` from the template and declare it in the year's
+/// module, creating the year module too if this is its first day.
+///
+/// Returns the files that were created or changed. Days outside the year's event,
+/// which has 12 days from 2025 on, are rejected.
+pub fn new_day(src_dir: &Path, year: usize, day: usize) -> io::Result<Vec<PathBuf>> {
+    let puzzle_day = match (year.try_into(), day.try_into()) {
+        (Ok(year), Ok(day)) => aoc_common::is_puzzle_day(year, day),
+        _ => false,
+    };
+    if !puzzle_day {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("there is no puzzle on y{:04} d{:02}", year, day),
+        ));
+    }
+
+    let mut changed = Vec::new();
+
+    let year_name = format!("y{:04}", year);
+    let year_dir = src_dir.join(&year_name);
+    let mod_path = year_dir.join("mod.rs");
+    if !mod_path.exists() {
+        fs::create_dir_all(&year_dir).map_err(at_path(&year_dir))?;
+        fs::write(&mod_path, "").map_err(at_path(&mod_path))?;
+        changed.push(mod_path.clone());
+
+        let lib_path = src_dir.join("lib.rs");
+        let years = Regex::new(r"^pub mod (y\d+);$").unwrap();
+        add_module(
+            &lib_path,
+            &format!("pub mod {};", year_name),
+            &year_name,
+            &years,
+        )?;
+        changed.push(lib_path);
+    }
+
+    let day_name = format!("day{:02}", day);
+    let day_path = year_dir.join(format!("{}.rs", day_name));
+    let mut file = fs::File::create_new(&day_path).map_err(at_path(&day_path))?;
+    file.write_all(render(year, day).as_bytes())
+        .map_err(at_path(&day_path))?;
+    changed.push(day_path);
+
+    // Follow the year's existing visibility for its days.
+    let days = Regex::new(r"^(?:pub )?mod (day\d+);$").unwrap();
+    let mod_contents = fs::read_to_string(&mod_path).map_err(at_path(&mod_path))?;
+    let visibility = if mod_contents
+        .lines()
+        .any(|line| line.starts_with("pub mod day"))
+    {
+        "pub "
+    } else {
+        ""
+    };
+    add_module(
+        &mod_path,
+        &format!("{}mod {};", visibility, day_name),
+        &day_name,
+        &days,
+    )?;
+    if !changed.contains(&mod_path) {
+        changed.push(mod_path);
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let days = Regex::new(r"^(?:pub )?mod (day\d+);$").unwrap();
+        assert_eq!(
+            insert_sorted("mod day01;\nmod day03;\n", "mod day02;", "day02", &days),
+            "mod day01;\nmod day02;\nmod day03;\n"
+        );
+        assert_eq!(
+            insert_sorted("mod day01;\n", "mod day02;", "day02", &days),
+            "mod day01;\nmod day02;\n"
+        );
+        assert_eq!(
+            insert_sorted("", "mod day02;", "day02", &days),
+            "mod day02;\n"
+        );
+
+        let years = Regex::new(r"^pub mod (y\d+);$").unwrap();
+        assert_eq!(
+            insert_sorted(
+                "pub mod y2024;\n\n#[cfg(test)]\nmod tests {}\n",
+                "pub mod y2025;",
+                "y2025",
+                &years
+            ),
+            "pub mod y2024;\npub mod y2025;\n\n#[cfg(test)]\nmod tests {}\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day_outside_event() {
+        let src_dir = Path::new("does-not-exist");
+        let error = new_day(src_dir, 2025, 13).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(new_day(src_dir, 2014, 1).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let source = render(2025, 12);
+        assert!(source.contains("parsed_aoc!(2025, 12, parse, part1, part2,)"));
+        assert!(!source.contains("__"));
+    }
+}
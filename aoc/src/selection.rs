@@ -0,0 +1,49 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::Aoc;
+
+/// Which of the registered solutions to run: everything, a year, or some days of a year.
+#[derive(Debug, Clone, Default, PartialEq, clap::Args)]
+pub struct Selection {
+    /// Only run solutions from this year
+    pub year: Option<usize>,
+
+    /// Only run these days of the year, either a single day or a range such as 1..=10
+    #[arg(requires = "year", value_parser = aoc_common::parse_days::<usize>)]
+    pub days: Option<RangeInclusive<usize>>,
+}
+
+impl Selection {
+    pub fn matches(&self, solution: &Aoc) -> bool {
+        self.year.is_none_or(|year| year == solution.year)
+            && self
+                .days
+                .as_ref()
+                .is_none_or(|days| days.contains(&solution.day))
+    }
+
+    /// The selected solutions, in order of year then day.
+    pub fn solutions(&self) -> Vec<&'static Aoc> {
+        let mut solutions: Vec<&'static Aoc> = inventory::iter::<Aoc>
+            .into_iter()
+            .filter(|solution| self.matches(solution))
+            .collect();
+        solutions.sort_by_key(|solution| (solution.year, solution.day));
+        solutions
+    }
+}
+
+impl Display for Selection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.year, &self.days) {
+            (None, _) => write!(f, "all years"),
+            (Some(year), None) => write!(f, "y{:04}", year),
+            (Some(year), Some(days)) if days.start() == days.end() => {
+                write!(f, "y{:04} d{:02}", year, days.start())
+            }
+            (Some(year), Some(days)) => {
+                write!(f, "y{:04} d{:02}..=d{:02}", year, days.start(), days.end())
+            }
+        }
+    }
+}
@@ -1,14 +0,0 @@
-use std::io::Read;
-
-use crate::Aoc;
-
-fn part1(buf: &mut dyn Read) {}
-
-fn part2(buf: &mut dyn Read) {}
-
-inventory::submit!(Aoc::new(
-    2023,
-    0,
-    part1,
-    part2,
-));
@@ -2,27 +2,27 @@ use std::io::{BufRead, BufReader, Read};
 
 use regex::Regex;
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Aoc, Error, Result};
 
-fn part1(buf: &mut dyn Read) {
+fn part1(buf: &mut dyn Read) -> Result<Answer> {
     let reader = BufReader::new(buf);
     let result: u32 = reader
         .lines()
-        .map_while(|line| {
-            let Ok(line) = line else {
-                return None;
-            };
-            let first_digit = line.chars().find(|c| c.is_numeric()).unwrap();
-            let last_digit = line.chars().filter(|c| c.is_numeric()).next_back().unwrap();
-            Some(
-                format!("{}{}", first_digit, last_digit)
-                    .parse::<u32>()
-                    .unwrap(),
-            )
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line?;
+            let first_digit = line
+                .chars()
+                .find(|c| c.is_numeric())
+                .ok_or_else(|| Error::parse("line has no digits").at_line(i + 1))?;
+            let last_digit = line.chars().rfind(|c| c.is_numeric()).unwrap();
+            format!("{}{}", first_digit, last_digit)
+                .parse::<u32>()
+                .at_line(i + 1)
         })
-        .sum();
+        .sum::<Result<u32>>()?;
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 fn convert_text(text: &str) -> &str {
@@ -40,7 +40,7 @@ fn convert_text(text: &str) -> &str {
     }
 }
 
-fn part2(buf: &mut dyn Read) {
+fn part2(buf: &mut dyn Read) -> Result<Answer> {
     let reader = BufReader::new(buf);
 
     // The line may read something like `123oneight`, which has an overlapping one and eight.
@@ -48,25 +48,24 @@ fn part2(buf: &mut dyn Read) {
     let re = Regex::new(r"^.*?(\d|one|two|three|four|five|six|seven|eight|nine).*(\d|one|two|three|four|five|six|seven|eight|nine).*?$|^.*?(\d|one|two|three|four|five|six|seven|eight|nine).*?$").unwrap();
     let result: u32 = reader
         .lines()
-        .map_while(|line| {
-            let Ok(line) = line else {
-                return None;
-            };
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line?;
 
-            let capture = re.captures(&line).unwrap();
+            let capture = re
+                .captures(&line)
+                .ok_or_else(|| Error::parse("line has no digits").at_line(i + 1))?;
             let first_digit = capture.get(1).or_else(|| capture.get(3)).unwrap().as_str();
             let last_digit = capture.get(2).or_else(|| capture.get(3)).unwrap().as_str();
             let first_digit = convert_text(first_digit);
             let last_digit = convert_text(last_digit);
-            Some(
-                format!("{}{}", first_digit, last_digit)
-                    .parse::<u32>()
-                    .unwrap(),
-            )
+            format!("{}{}", first_digit, last_digit)
+                .parse::<u32>()
+                .at_line(i + 1)
         })
-        .sum();
+        .sum::<Result<u32>>()?;
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
 inventory::submit!(Aoc::new(2023, 1, part1, part2,));
@@ -1,8 +1,8 @@
 use std::io::{BufRead, BufReader, Read};
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct Balls {
     red: usize,
     green: usize,
@@ -15,43 +15,51 @@ struct Game {
     picks: Vec<Balls>,
 }
 
-fn parse_games(buf: &mut dyn Read) -> Vec<Game> {
+fn parse_game(line: &str) -> Result<Game> {
+    let (game_name, picks) = line
+        .split_once(':')
+        .ok_or_else(|| Error::parse("expected 'Game <id>:'"))?;
+    let id = game_name
+        .split_once(' ')
+        .ok_or_else(|| Error::parse("expected 'Game <id>:'"))?
+        .1
+        .parse()?;
+    let picks = picks
+        .split(';')
+        .map(|pick| {
+            let balls = pick.split(',');
+            let mut red = 0;
+            let mut green = 0;
+            let mut blue = 0;
+
+            for ball in balls {
+                let (count, colour) = ball.trim().split_once(' ').ok_or_else(|| {
+                    Error::parse(format!(
+                        "expected '<count> <colour>', got '{}'",
+                        ball.trim()
+                    ))
+                })?;
+                let count: usize = count.parse()?;
+                match colour {
+                    "red" => red = count,
+                    "green" => green = count,
+                    "blue" => blue = count,
+                    _ => return Err(Error::parse(format!("unknown colour '{}'", colour))),
+                };
+            }
+            Ok(Balls { red, green, blue })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Game { id, picks })
+}
+
+fn parse_games(buf: &mut dyn Read) -> Result<Vec<Game>> {
     let reader = BufReader::new(buf);
     reader
         .lines()
-        .map_while(|line| {
-            let Ok(line) = line else {
-                return None;
-            };
-            let (game_name, picks) = line.split_once(':').unwrap();
-            let id = game_name.split_once(' ').unwrap().1.parse().unwrap();
-            let picks = picks
-                .split(';')
-                .map(|pick| {
-                    let balls = pick.split(',');
-                    let mut red = 0;
-                    let mut green = 0;
-                    let mut blue = 0;
-
-                    for ball in balls {
-                        let (count, colour) = ball.trim().split_once(' ').unwrap();
-                        let count: usize = count.parse().unwrap();
-                        match colour {
-                            "red" => red = count,
-                            "green" => green = count,
-                            "blue" => blue = count,
-                            _ => {
-                                dbg!(colour);
-                            }
-                        };
-                    }
-                    Balls { red, green, blue }
-                })
-                .collect();
-
-            dbg!(line);
-            dbg!(Some(Game { id, picks }))
-        })
+        .enumerate()
+        .map(|(i, line)| parse_game(&line?).at_line(i + 1))
         .collect()
 }
 
@@ -61,24 +69,22 @@ fn is_valid_game(game: &Game, max_red: usize, max_green: usize, max_blue: usize)
         .all(|pick| pick.red <= max_red && pick.blue <= max_blue && pick.green <= max_green)
 }
 
-fn part1(buf: &mut dyn Read) {
-    let games = parse_games(buf);
-
+fn part1(games: &[Game]) -> Result<Answer> {
     let result = games
         .iter()
         .filter_map(|game| is_valid_game(game, 12, 13, 14).then_some(game.id))
         .sum::<usize>();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let games = parse_games(buf);
+fn part2(games: &[Game]) -> Result<Answer> {
     let result = games
-        .into_iter()
+        .iter()
         .map(|game| {
             game.picks
-                .into_iter()
+                .iter()
+                .copied()
                 .reduce(|acc, next| Balls {
                     red: acc.red.max(next.red),
                     green: acc.green.max(next.green),
@@ -89,7 +95,7 @@ fn part2(buf: &mut dyn Read) {
         .map(|min_balls| min_balls.red * min_balls.green * min_balls.blue)
         .sum::<usize>();
 
-    println!("Part 2: {}", result)
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2023, 2, part1, part2,));
+inventory::submit!(parsed_aoc!(2023, 2, parse_games, part1, part2,));
@@ -5,18 +5,26 @@ use std::{
 
 use grid::{Grid, Location};
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Grid<char> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<char>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
-        line_length = line.len();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let length = line.chars().count();
+        if i > 0 && length != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length, length
+            ))
+            .at_line(i + 1));
+        }
+        line_length = length;
         lines.push(line);
     }
-    Grid::new(lines.join("").chars(), line_length)
+    Ok(Grid::new(lines.join("").chars(), line_length))
 }
 
 fn adjacent_locations(location: Location) -> Vec<Location> {
@@ -38,17 +46,19 @@ fn adjacent_locations(location: Location) -> Vec<Location> {
 fn find_symbols(grid: &Grid<char>) -> impl Iterator<Item = Location> + '_ {
     grid.iter_locations().filter(|location| {
         let char = grid.get(location).unwrap();
-        !char.is_numeric() && *char != '.'
+        !char.is_ascii_digit() && *char != '.'
     })
 }
 
 fn find_adjacent_numbers(
     grid: &Grid<char>,
     location: Location,
-) -> impl Iterator<Item = (usize, Location)> + '_ {
-    let adjacent_number_locations = adjacent_locations(location)
-        .into_iter()
-        .filter(|location| grid.get(location).map(|v| v.is_numeric()).unwrap_or(false));
+) -> impl Iterator<Item = Result<(usize, Location)>> + '_ {
+    let adjacent_number_locations = adjacent_locations(location).into_iter().filter(|location| {
+        grid.get(location)
+            .map(|v| v.is_ascii_digit())
+            .unwrap_or(false)
+    });
     let mut scanned_cells = HashSet::new();
 
     adjacent_number_locations.filter_map(move |number_location| {
@@ -62,7 +72,7 @@ fn find_adjacent_numbers(
         let mut number_start = number_location.clone();
         while number_start
             .left()
-            .and_then(|location| grid.get(&location).map(|char| char.is_numeric()))
+            .and_then(|location| grid.get(&location).map(|char| char.is_ascii_digit()))
             .unwrap_or(false)
         {
             number_start = number_start.left().unwrap();
@@ -73,7 +83,7 @@ fn find_adjacent_numbers(
         let mut number_end = number_location.clone();
         while grid
             .get(&number_end.right())
-            .map(|char| char.is_numeric())
+            .map(|char| char.is_ascii_digit())
             .unwrap_or(false)
         {
             number_end = number_end.right();
@@ -83,39 +93,54 @@ fn find_adjacent_numbers(
 
         let number_string: String = number_chars.into_iter().collect();
 
-        Some((number_string.parse::<usize>().unwrap(), number_start))
+        let number = number_string
+            .parse::<usize>()
+            .map_err(Error::from)
+            .at(number_start.y + 1, number_start.x + 1);
+        Some(number.map(|number| (number, number_start)))
     })
 }
 
-fn part1(buf: &mut dyn Read) {
-    let grid = parse(buf);
-    let symbol_locations = find_symbols(&grid);
+fn part1(grid: &Grid<char>) -> Result<Answer> {
+    let symbol_locations = find_symbols(grid);
 
     let numbers: HashSet<_> = symbol_locations
-        .flat_map(|location| find_adjacent_numbers(&grid, location))
-        .collect();
+        .flat_map(|location| find_adjacent_numbers(grid, location))
+        .collect::<Result<_>>()?;
     let result: usize = numbers.into_iter().map(|(v, _)| v).sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let grid = parse(buf);
-    let symbol_locations = find_symbols(&grid);
+fn part2(grid: &Grid<char>) -> Result<Answer> {
+    let symbol_locations = find_symbols(grid);
 
-    let numbers = symbol_locations.filter_map(|location| {
-        let adjacent_numbers: Vec<_> = find_adjacent_numbers(&grid, location)
-            .map(|(v, _)| v)
-            .collect();
+    let mut result = 0;
+    for location in symbol_locations {
+        let adjacent_numbers: Vec<_> = find_adjacent_numbers(grid, location)
+            .map(|number| number.map(|(v, _)| v))
+            .collect::<Result<_>>()?;
         if adjacent_numbers.len() == 2 {
-            Some(adjacent_numbers.into_iter().product::<usize>())
-        } else {
-            None
+            result += adjacent_numbers.into_iter().product::<usize>();
         }
-    });
-    let result: usize = numbers.sum();
+    }
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2023, 3, part1, part2,));
+inventory::submit!(parsed_aoc!(2023, 3, parse, part1, part2,));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let grid = parse(&mut "12*٣4\n".as_bytes()).unwrap();
+        assert_eq!(part1(&grid).unwrap(), Answer::Number(16));
+
+        let grid = parse(&mut "*....................\n123456789012345678901\n".as_bytes()).unwrap();
+        let error = part1(&grid).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(1)));
+    }
+}
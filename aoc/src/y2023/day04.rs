@@ -3,7 +3,7 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 struct Card {
@@ -12,43 +12,52 @@ struct Card {
     winning_numbers: Vec<usize>,
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<Card> {
+fn parse_card(line: &str) -> Result<Card> {
+    let (label, numbers) = line
+        .split_once(':')
+        .ok_or_else(|| Error::parse("expected 'Card <id>:'"))?;
+    let id: usize = label
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| Error::parse("expected 'Card <id>:'"))?
+        .1
+        .trim()
+        .parse()?;
+    let (numbers, winning_numbers) = numbers
+        .split_once('|')
+        .ok_or_else(|| Error::parse("expected '|' between the number lists"))?;
+    let numbers: Vec<usize> = numbers
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|v| v.parse())
+        .collect::<std::result::Result<_, _>>()?;
+    let winning_numbers: Vec<usize> = winning_numbers
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|v| v.parse())
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(Card {
+        id,
+        numbers,
+        winning_numbers,
+    })
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Card>> {
     let reader = BufReader::new(buf);
     reader
         .lines()
-        .map_while(|line| {
-            let line = line.ok()?;
-            let (label, numbers) = line.split_once(':')?;
-            let id: usize = label.trim().split_once(' ')?.1.trim().parse().ok()?;
-            let (numbers, winning_numbers) = numbers.split_once('|')?;
-            let numbers: Vec<usize> = numbers
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .map(|v| v.parse().unwrap())
-                .collect();
-            let winning_numbers: Vec<usize> = winning_numbers
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .map(|v| v.parse().unwrap())
-                .collect();
-            Some(Card {
-                id,
-                numbers,
-                winning_numbers,
-            })
-        })
+        .enumerate()
+        .map(|(i, line)| parse_card(&line?).at_line(i + 1))
         .collect()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let cards = parse(buf);
-
+fn part1(cards: &[Card]) -> Result<Answer> {
     let result: usize = cards
-        .into_iter()
+        .iter()
         .map(|card| {
-            dbg!(&card);
-            let numbers: HashSet<_> = card.numbers.into_iter().collect();
-            let winning_numbers: HashSet<_> = card.winning_numbers.into_iter().collect();
+            let numbers: HashSet<_> = card.numbers.iter().collect();
+            let winning_numbers: HashSet<_> = card.winning_numbers.iter().collect();
             let count = numbers.intersection(&winning_numbers).count();
             if count == 0 {
                 0
@@ -58,20 +67,19 @@ fn part1(buf: &mut dyn Read) {
         })
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let cards = parse(buf);
+fn part2(cards: &[Card]) -> Result<Answer> {
     let mut won_cards: HashMap<usize, usize> = cards
         .iter()
         .enumerate()
         .map(|(index, _)| (index, 1))
         .collect();
 
-    for (i, card) in cards.into_iter().enumerate() {
-        let numbers: HashSet<_> = card.numbers.into_iter().collect();
-        let winning_numbers: HashSet<_> = card.winning_numbers.into_iter().collect();
+    for (i, card) in cards.iter().enumerate() {
+        let numbers: HashSet<_> = card.numbers.iter().collect();
+        let winning_numbers: HashSet<_> = card.winning_numbers.iter().collect();
         let count = numbers.intersection(&winning_numbers).count();
         let score = *won_cards.get(&i).unwrap();
         for j in 0..count {
@@ -81,7 +89,7 @@ fn part2(buf: &mut dyn Read) {
 
     let result: usize = won_cards.values().sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2023, 4, part1, part2,));
+inventory::submit!(parsed_aoc!(2023, 4, parse, part1, part2,));
@@ -1,4 +1,4 @@
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 use regex::Regex;
 use std::io::BufRead;
 use std::{collections::HashMap, io::BufReader};
@@ -8,35 +8,34 @@ struct Data {
     list_b: Vec<u32>,
 }
 
-fn parse(buf: &mut dyn std::io::Read) -> Data {
+fn parse(buf: &mut dyn std::io::Read) -> Result<Data> {
     let mut list_a = Vec::<u32>::new();
     let mut list_b = Vec::<u32>::new();
 
     let reader = BufReader::new(buf);
     let separator_re = Regex::new(" +").unwrap();
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         if line.is_empty() {
             break;
         }
         let [a, b] = separator_re
             .split(&line)
-            .map(|v| v.parse::<u32>().unwrap())
-            .collect::<Vec<_>>()
+            .map(|v| v.parse::<u32>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .at_line(i + 1)?
             .try_into()
-            .unwrap();
+            .map_err(|_| Error::parse("expected two numbers").at_line(i + 1))?;
         list_a.push(a);
         list_b.push(b);
     }
 
-    Data { list_a, list_b }
+    Ok(Data { list_a, list_b })
 }
 
-fn part1(buf: &mut dyn std::io::Read) {
-    let Data {
-        mut list_a,
-        mut list_b,
-    } = parse(buf);
+fn part1(data: &Data) -> Result<Answer> {
+    let mut list_a = data.list_a.clone();
+    let mut list_b = data.list_b.clone();
     list_a.sort();
     list_b.sort();
 
@@ -45,21 +44,21 @@ fn part1(buf: &mut dyn std::io::Read) {
         .zip(list_b)
         .map(|(a, b)| a.abs_diff(b))
         .sum();
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn count(list: Vec<u32>) -> HashMap<u32, u32> {
+fn count(list: &[u32]) -> HashMap<u32, u32> {
     let mut counter = HashMap::new();
 
     for item in list {
-        *counter.entry(item).or_default() += 1;
+        *counter.entry(*item).or_default() += 1;
     }
 
     counter
 }
 
-fn part2(buf: &mut dyn std::io::Read) {
-    let Data { list_a, list_b } = parse(buf);
+fn part2(data: &Data) -> Result<Answer> {
+    let Data { list_a, list_b } = data;
 
     let count_a = count(list_a);
     let count_b = count(list_b);
@@ -68,9 +67,9 @@ fn part2(buf: &mut dyn std::io::Read) {
         .into_iter()
         .map(|(n, count)| n * count * count_b.get(&n).cloned().unwrap_or(0))
         .sum();
-    println!("Part 2: {}", score);
+    Ok(score.into())
 }
-inventory::submit!(Aoc::new(2024, 1, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 1, parse, part1, part2,));
 
 // fn main() {
 //     part1(list_a.clone(), list_b.clone());
@@ -1,21 +1,26 @@
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Result};
 use std::io::{BufRead, BufReader, Read};
 
 type Levels = Vec<u8>;
 type Report = Vec<Levels>;
 
-fn parse(buf: &mut dyn Read) -> Report {
+fn parse(buf: &mut dyn Read) -> Result<Report> {
     let reader = BufReader::new(buf);
 
     let mut report: Report = Vec::new();
 
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
 
-        report.push(line.split(' ').map(|v| v.parse().unwrap()).collect())
+        report.push(
+            line.split(' ')
+                .map(|v| v.parse())
+                .collect::<std::result::Result<_, _>>()
+                .at_line(i + 1)?,
+        )
     }
 
-    report
+    Ok(report)
 }
 
 fn is_safe(levels: Levels) -> bool {
@@ -61,12 +66,10 @@ fn is_safe(levels: Levels) -> bool {
     true
 }
 
-fn part1(buf: &mut dyn Read) {
-    let report: Vec<Vec<u8>> = parse(buf);
+fn part1(report: &Report) -> Result<Answer> {
+    let safe_count = report.iter().cloned().map(is_safe).filter(|v| *v).count();
 
-    let safe_count = report.into_iter().map(is_safe).filter(|v| *v).count();
-
-    println!("Part 1: {}", safe_count);
+    Ok(safe_count.into())
 }
 
 fn is_safeish(levels: Levels) -> bool {
@@ -85,17 +88,16 @@ fn is_safeish(levels: Levels) -> bool {
     false
 }
 
-fn part2(buf: &mut dyn Read) {
-    let report: Vec<Vec<u8>> = parse(buf);
+fn part2(report: &Report) -> Result<Answer> {
     let safeish_count = report
-        .into_iter()
+        .iter()
         .map(|levels| is_safeish(levels.clone()))
         .filter(|v| *v)
         .count();
 
-    println!("Part 2: {}", safeish_count);
+    Ok(safeish_count.into())
 }
-inventory::submit!(Aoc::new(2024, 2, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 2, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -1,23 +1,20 @@
-use crate::Aoc;
+use crate::{Answer, Result};
 use std::io::Read;
 
 use regex::bytes::Regex;
 
-fn parse(buf: &mut dyn Read) -> Vec<u8> {
-    let mut buffer = vec![0u8; 4 * 1024 * 1024];
-    let len = buf.read(&mut buffer).unwrap();
-    buffer.shrink_to(len);
+fn parse(buf: &mut dyn Read) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    buf.read_to_end(&mut buffer)?;
 
-    buffer
+    Ok(buffer)
 }
 
-fn part1(buf: &mut dyn Read) {
-    let buffer = parse(buf);
-
+fn part1(buffer: &[u8]) -> Result<Answer> {
     let regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
 
     let result: u64 = regex
-        .captures_iter(&buffer)
+        .captures_iter(buffer)
         .map(|c| {
             let a: u64 = String::from_utf8(c.get(1).unwrap().as_bytes().to_vec())
                 .unwrap()
@@ -32,13 +29,11 @@ fn part1(buf: &mut dyn Read) {
         })
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
-inventory::submit!(Aoc::new(2024, 3, part1, part2,));
-
-fn part2(buf: &mut dyn Read) {
-    let buffer = parse(buf);
+inventory::submit!(parsed_aoc!(2024, 3, parse, part1, part2,));
 
+fn part2(buffer: &[u8]) -> Result<Answer> {
     let regex = Regex::new(r"do\(\)|don't\(\)|mul\((\d{1,3}),(\d{1,3})\)").unwrap();
 
     const DO_STR: &[u8] = b"do()";
@@ -46,7 +41,7 @@ fn part2(buf: &mut dyn Read) {
 
     let mut do_mul = true;
     let result: u64 = regex
-        .captures_iter(&buffer)
+        .captures_iter(buffer)
         .map(|c| {
             if c.get(0).unwrap().as_bytes() == DO_STR {
                 do_mul = true;
@@ -73,5 +68,5 @@ fn part2(buf: &mut dyn Read) {
         })
         .sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
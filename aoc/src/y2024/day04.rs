@@ -1,18 +1,26 @@
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 use std::io::{BufRead, BufReader, Read};
 
 use grid::{Grid, Location};
 
-fn parse(buf: &mut dyn Read) -> Grid<char> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<char>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i > 0 && line.len() != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length,
+                line.len()
+            ))
+            .at_line(i + 1));
+        }
         line_length = line.len();
         lines.push(line);
     }
-    Grid::new(lines.join("").chars(), line_length)
+    Ok(Grid::new(lines.join("").chars(), line_length))
 }
 
 fn check_down(grid: &Grid<char>, search_text: &str, start: &Location) -> bool {
@@ -132,57 +140,55 @@ fn check_diagonal_up_left(grid: &Grid<char>, search_text: &str, start: &Location
     }
 }
 
-fn part1(buf: &mut dyn Read) {
-    let body = parse(buf);
+fn part1(body: &Grid<char>) -> Result<Answer> {
     let mut hit_count = 0;
     for location in body.iter_locations() {
         let hits = [
-            check_down(&body, "XMAS", &location),
-            check_right(&body, "XMAS", &location),
-            check_diagonal_left(&body, "XMAS", &location),
-            check_diagonal_right(&body, "XMAS", &location),
-            check_down(&body, "SAMX", &location),
-            check_right(&body, "SAMX", &location),
-            check_diagonal_left(&body, "SAMX", &location),
-            check_diagonal_right(&body, "SAMX", &location),
+            check_down(body, "XMAS", &location),
+            check_right(body, "XMAS", &location),
+            check_diagonal_left(body, "XMAS", &location),
+            check_diagonal_right(body, "XMAS", &location),
+            check_down(body, "SAMX", &location),
+            check_right(body, "SAMX", &location),
+            check_diagonal_left(body, "SAMX", &location),
+            check_diagonal_right(body, "SAMX", &location),
         ];
 
         let hits = hits.into_iter().filter(|hit| *hit).count();
         hit_count += hits
     }
 
-    println!("Part 1: {}", hit_count);
+    Ok(hit_count.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let body = parse(buf);
+fn part2(body: &Grid<char>) -> Result<Answer> {
     let mut hit_count = 0;
     for location in body.iter_locations() {
-        let hit = check_diagonal_up_left(&body, "AS", &location)
-            && check_diagonal_up_right(&body, "AS", &location)
-            && check_diagonal_left(&body, "AM", &location)
-            && check_diagonal_right(&body, "AM", &location)
-            || check_diagonal_up_left(&body, "AM", &location)
-                && check_diagonal_up_right(&body, "AS", &location)
-                && check_diagonal_left(&body, "AM", &location)
-                && check_diagonal_right(&body, "AS", &location)
-            || check_diagonal_up_left(&body, "AM", &location)
-                && check_diagonal_up_right(&body, "AM", &location)
-                && check_diagonal_left(&body, "AS", &location)
-                && check_diagonal_right(&body, "AS", &location)
-            || check_diagonal_up_left(&body, "AS", &location)
-                && check_diagonal_up_right(&body, "AM", &location)
-                && check_diagonal_left(&body, "AS", &location)
-                && check_diagonal_right(&body, "AM", &location);
+        let hit = check_diagonal_up_left(body, "AS", &location)
+            && check_diagonal_up_right(body, "AS", &location)
+            && check_diagonal_left(body, "AM", &location)
+            && check_diagonal_right(body, "AM", &location)
+            || check_diagonal_up_left(body, "AM", &location)
+                && check_diagonal_up_right(body, "AS", &location)
+                && check_diagonal_left(body, "AM", &location)
+                && check_diagonal_right(body, "AS", &location)
+            || check_diagonal_up_left(body, "AM", &location)
+                && check_diagonal_up_right(body, "AM", &location)
+                && check_diagonal_left(body, "AS", &location)
+                && check_diagonal_right(body, "AS", &location)
+            || check_diagonal_up_left(body, "AS", &location)
+                && check_diagonal_up_right(body, "AM", &location)
+                && check_diagonal_left(body, "AS", &location)
+                && check_diagonal_right(body, "AM", &location);
 
         if hit {
             hit_count += 1;
         }
     }
 
-    println!("Part 2: {}", hit_count);
+    Ok(hit_count.into())
 }
-inventory::submit!(Aoc::new(2024, 4, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 4, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -1,4 +1,4 @@
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
@@ -14,37 +14,37 @@ struct Data {
     updates: Vec<Pages>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
+fn parse(buf: &mut dyn Read) -> Result<Data> {
     let reader = BufReader::new(buf);
     let mut rules: Rules = HashMap::new();
     let mut updates: Vec<Pages> = Vec::new();
 
     let mut is_pages = false;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         if line.is_empty() {
             is_pages = true;
             continue;
         }
 
         if !is_pages {
-            let numbers: Vec<usize> = line
-                .split('|')
-                .map(|v| v.parse::<usize>().unwrap())
-                .collect();
-            let before = numbers[0];
-            let after = numbers[1];
+            let (before, after) = line
+                .split_once('|')
+                .ok_or_else(|| Error::parse("expected a '<before>|<after>' rule").at_line(i + 1))?;
+            let before: usize = before.parse().at_line(i + 1)?;
+            let after: usize = after.parse().at_line(i + 1)?;
             rules.entry(after).or_default().insert(before);
         } else {
             let numbers = line
                 .split(',')
-                .map(|v| v.parse::<usize>().unwrap())
-                .collect();
+                .map(|v| v.parse::<usize>())
+                .collect::<std::result::Result<_, _>>()
+                .at_line(i + 1)?;
             updates.push(numbers);
         }
     }
 
-    Data { rules, updates }
+    Ok(Data { rules, updates })
 }
 
 fn is_valid(rules: &Rules, update: &[usize]) -> bool {
@@ -67,14 +67,14 @@ fn get_middle_page(update: &[usize]) -> usize {
     update[update.len() / 2]
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Data { rules, updates } = parse(buf);
+fn part1(data: &Data) -> Result<Answer> {
+    let Data { rules, updates } = data;
     let result: usize = updates
         .iter()
-        .filter(|update| is_valid(&rules, update))
+        .filter(|update| is_valid(rules, update))
         .map(|update| get_middle_page(update))
         .sum();
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 fn reorder_pages(rules: &Rules, rules_rev: &Rules, update: &[usize]) -> Vec<usize> {
@@ -99,27 +99,27 @@ fn reorder_pages(rules: &Rules, rules_rev: &Rules, update: &[usize]) -> Vec<usiz
     update
 }
 
-fn part2(buf: &mut dyn Read) {
-    let Data { rules, updates } = parse(buf);
+fn part2(data: &Data) -> Result<Answer> {
+    let Data { rules, updates } = data;
     let mut rules_rev: Rules = HashMap::new();
-    for (after, befores) in &rules {
+    for (after, befores) in rules {
         for before in befores {
             rules_rev.entry(*before).or_default().insert(*after);
         }
     }
     let bad_updates: Vec<&Pages> = updates
         .iter()
-        .filter(|update| !is_valid(&rules, update))
+        .filter(|update| !is_valid(rules, update))
         .collect();
     let result: usize = bad_updates
         .into_iter()
-        .map(|update| reorder_pages(&rules, &rules_rev, update))
+        .map(|update| reorder_pages(rules, &rules_rev, update))
         .map(|update| get_middle_page(&update))
         .sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
-inventory::submit!(Aoc::new(2024, 5, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 5, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
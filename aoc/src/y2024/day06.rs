@@ -1,20 +1,28 @@
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 use grid::{Grid, Location};
 use std::{
     collections::HashSet,
     io::{BufRead, BufReader, Read},
 };
 
-fn parse(buf: &mut dyn Read) -> Grid<char> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<char>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i > 0 && line.len() != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length,
+                line.len()
+            ))
+            .at_line(i + 1));
+        }
         line_length = line.len();
         lines.push(line);
     }
-    Grid::new(lines.join("").chars(), line_length)
+    Ok(Grid::new(lines.join("").chars(), line_length))
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -25,20 +33,24 @@ enum Direction {
     Left,
 }
 
-fn part1(buf: &mut dyn Read) {
-    let grid = &parse(buf);
-    let path = find_path(grid).unwrap();
+fn part1(grid: &Grid<char>) -> Result<Answer> {
+    let start = find_start(grid)?;
+    let path = find_path(grid, &start)
+        .ok_or_else(|| Error::no_solution("the guard never leaves the map"))?;
     let set: HashSet<Location> = HashSet::from_iter(path);
-    println!("Part 1: {}", set.len());
+    Ok(set.len().into())
+}
+
+fn find_start(grid: &Grid<char>) -> Result<Location> {
+    grid.iter_locations()
+        .find(|location| grid.get(location) == Some(&'^'))
+        .ok_or_else(|| Error::parse("no guard '^' in the map"))
 }
 
-fn find_path(grid: &Grid<char>) -> Option<Vec<Location>> {
+fn find_path(grid: &Grid<char>, start: &Location) -> Option<Vec<Location>> {
     let grid = grid.clone();
     let mut direction = Direction::Up;
-    let mut position = grid
-        .iter_locations()
-        .find(|location| grid.get(location) == Some(&'^'))
-        .unwrap();
+    let mut position = start.clone();
 
     let mut path = vec![position.clone()];
     let mut visited_cells = HashSet::new();
@@ -78,15 +90,16 @@ fn find_path(grid: &Grid<char>) -> Option<Vec<Location>> {
     Some(path)
 }
 
-fn check_for_loop(grid: &Grid<char>) -> bool {
-    find_path(grid).is_none()
+fn check_for_loop(grid: &Grid<char>, start: &Location) -> bool {
+    find_path(grid, start).is_none()
 }
 
-fn part2(buf: &mut dyn Read) {
-    let grid = &parse(buf);
-
+fn part2(grid: &Grid<char>) -> Result<Answer> {
     let mut valid_obstruction_locations = HashSet::new();
-    let path: HashSet<Location> = HashSet::from_iter(find_path(grid).unwrap());
+    let start = find_start(grid)?;
+    let path = find_path(grid, &start)
+        .ok_or_else(|| Error::no_solution("the guard never leaves the map"))?;
+    let path: HashSet<Location> = HashSet::from_iter(path);
     for obstruction_location in path {
         // eprintln!("Checking {:?}", obstruction_location);
         let mut grid = grid.clone();
@@ -102,13 +115,13 @@ fn part2(buf: &mut dyn Read) {
 
         grid.set(&obstruction_location, '#');
 
-        let has_loop = check_for_loop(&grid);
+        let has_loop = check_for_loop(&grid, &start);
         if has_loop {
             valid_obstruction_locations.insert(obstruction_location);
         }
     }
 
-    println!("Part 2: {}", valid_obstruction_locations.len())
+    Ok(valid_obstruction_locations.len().into())
 }
 
-inventory::submit!(Aoc::new(2024, 6, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 6, parse, part1, part2,));
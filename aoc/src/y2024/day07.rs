@@ -1,4 +1,4 @@
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 use std::io::{BufRead, BufReader, Read};
 
 #[derive(Debug, Clone)]
@@ -7,32 +7,32 @@ struct Calibration {
     numbers: Vec<usize>,
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<Calibration> {
+fn parse(buf: &mut dyn Read) -> Result<Vec<Calibration>> {
     let reader = BufReader::new(buf);
     let mut calibrations = Vec::new();
 
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let (target, numbers) = line.split_once(':').unwrap();
-        let target: usize = target.parse().unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let (target, numbers) = line
+            .split_once(':')
+            .ok_or_else(|| Error::parse("expected '<target>: <numbers>'").at_line(i + 1))?;
+        let target: usize = target.parse().at_line(i + 1)?;
         let numbers: Vec<usize> = numbers
             .trim()
             .split(' ')
-            .map(|n| (n.trim()).parse().unwrap())
-            .collect();
+            .map(|n| (n.trim()).parse())
+            .collect::<std::result::Result<_, _>>()
+            .at_line(i + 1)?;
         calibrations.push(Calibration { target, numbers });
     }
 
-    calibrations
+    Ok(calibrations)
 }
 
 fn is_solvable(target: usize, numbers: &[usize], allow_concat: bool) -> bool {
-    if numbers.is_empty() {
+    let Some((last, remaining)) = numbers.split_last() else {
         return target == 0;
-    }
-
-    let last = numbers.last().unwrap();
-    let remaining = &numbers[..numbers.len() - 1];
+    };
     if target < *last {
         return false;
     }
@@ -42,11 +42,13 @@ fn is_solvable(target: usize, numbers: &[usize], allow_concat: bool) -> bool {
         return true;
     }
 
-    if target.is_multiple_of(*last) {
-        let mul_target = target / last;
-        if is_solvable(mul_target, remaining, allow_concat) {
-            return true;
-        }
+    // Multiplying by zero gives zero whatever came before
+    let mul_solvable = match *last {
+        0 => target == 0,
+        last => target.is_multiple_of(last) && is_solvable(target / last, remaining, allow_concat),
+    };
+    if mul_solvable {
+        return true;
     }
 
     // Concat check
@@ -64,26 +66,24 @@ fn is_solvable(target: usize, numbers: &[usize], allow_concat: bool) -> bool {
     false
 }
 
-fn part1(buf: &mut dyn Read) {
-    let calibrations = parse(buf);
+fn part1(calibrations: &[Calibration]) -> Result<Answer> {
     let result: usize = calibrations
         .iter()
         .filter_map(|cal| is_solvable(cal.target, &cal.numbers, false).then_some(cal.target))
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let calibrations = parse(buf);
+fn part2(calibrations: &[Calibration]) -> Result<Answer> {
     let result: usize = calibrations
         .iter()
         .filter_map(|cal| is_solvable(cal.target, &cal.numbers, true).then_some(cal.target))
         .sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
-inventory::submit!(Aoc::new(2024, 7, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 7, parse, part1, part2,));
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +99,7 @@ mod tests {
         assert!(!is_solvable(192, &[17, 8, 14], false));
         assert!(!is_solvable(21037, &[9, 7, 18, 13], false));
         assert!(is_solvable(292, &[11, 6, 16, 20], false));
+        assert!(is_solvable(0, &[5, 0], false));
     }
 
     #[test]
@@ -113,4 +114,14 @@ mod tests {
         assert!(!is_solvable(21037, &[9, 7, 18, 13], true));
         assert!(is_solvable(292, &[11, 6, 16, 20], true));
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(&mut "190: 10 19\n".as_bytes()).unwrap()[0].numbers,
+            [10, 19]
+        );
+        assert!(parse(&mut "190:\n".as_bytes()).is_err());
+        assert!(parse(&mut "190 10 19\n".as_bytes()).is_err());
+    }
 }
@@ -4,18 +4,26 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Grid<char> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<char>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i > 0 && line.len() != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length,
+                line.len()
+            ))
+            .at_line(i + 1));
+        }
         line_length = line.len();
         lines.push(line);
     }
-    Grid::new(lines.join("").chars(), line_length)
+    Ok(Grid::new(lines.join("").chars(), line_length))
 }
 
 fn find_antennae_locations(grid: &Grid<char>) -> HashMap<char, HashSet<Location>> {
@@ -92,9 +100,8 @@ fn find_pairs(locations: impl Iterator<Item = Location>) -> Vec<(Location, Locat
     pairs
 }
 
-fn part1(buf: &mut dyn Read) {
-    let grid = parse(buf);
-    let antennae_locations = find_antennae_locations(&grid);
+fn part1(grid: &Grid<char>) -> Result<Answer> {
+    let antennae_locations = find_antennae_locations(grid);
     let antinode_locations: HashSet<Location> = antennae_locations
         .values()
         .flat_map(|locations| find_pairs(locations.iter().cloned()))
@@ -102,12 +109,11 @@ fn part1(buf: &mut dyn Read) {
         .filter(|location| grid.is_within_bounds(location))
         .collect();
 
-    println!("Part 1: {}", antinode_locations.len());
+    Ok(antinode_locations.len().into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let grid = parse(buf);
-    let antennae_locations = find_antennae_locations(&grid);
+fn part2(grid: &Grid<char>) -> Result<Answer> {
+    let antennae_locations = find_antennae_locations(grid);
     let antinode_locations: HashSet<Location> = antennae_locations
         .values()
         .flat_map(|locations| find_pairs(locations.iter().cloned()))
@@ -115,10 +121,10 @@ fn part2(buf: &mut dyn Read) {
         .filter(|location| grid.is_within_bounds(location))
         .collect();
 
-    println!("Part 2: {}", antinode_locations.len());
+    Ok(antinode_locations.len().into())
 }
 
-inventory::submit!(Aoc::new(2024, 8, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 8, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -1,14 +1,17 @@
-use std::{collections::VecDeque, io::Read};
+use std::{
+    io::{BufReader, Read},
+    iter,
+};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 struct File {
     id: usize,
     length: u8,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 struct Gap {
     length: u8,
 }
@@ -25,11 +28,12 @@ struct Data {
     gaps: Vec<Gap>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
-    let bytes = buf.bytes();
+fn parse(buf: &mut dyn Read) -> Result<Data> {
+    let bytes = BufReader::new(buf).bytes();
     let mut files = Vec::new();
     let mut gaps = Vec::new();
-    for (i, byte) in bytes.flatten().enumerate() {
+    for (i, byte) in bytes.enumerate() {
+        let byte = byte?;
         let length = match byte {
             b'0' => 0,
             b'1' => 1,
@@ -42,7 +46,12 @@ fn parse(buf: &mut dyn Read) -> Data {
             b'8' => 8,
             b'9' => 9,
             b'\n' => break,
-            _ => panic!("Byte not matched"),
+            _ => {
+                return Err(
+                    Error::parse(format!("expected a digit, found {:?}", byte as char))
+                        .at(1, i + 1),
+                )
+            }
         };
 
         if i % 2 == 0 {
@@ -52,50 +61,37 @@ fn parse(buf: &mut dyn Read) -> Data {
         }
     }
 
-    Data { files, gaps }
+    Ok(Data { files, gaps })
 }
 
-fn get_block_ids(files: Vec<File>, mut gaps: Vec<Gap>) -> impl Iterator<Item = usize> {
-    let mut files = VecDeque::from(files);
-    gaps.reverse();
-    let final_length: usize = files.iter().map(|f| f.length as usize).sum();
-
-    let mut file = files.pop_front().unwrap();
-    let mut end_file = files.pop_back().unwrap();
-    let mut gap = gaps.pop().unwrap();
-
-    (0..final_length).map(move |_| {
-        if file.length != 0 {
-            file.length -= 1;
-            file.id
-        } else if gap.length != 0 {
-            gap.length -= 1;
-            if end_file.length == 0 {
-                end_file = files.pop_back().unwrap();
-            }
-            end_file.length -= 1;
-            end_file.id
-        } else if !files.is_empty() {
-            file = files.pop_front().unwrap();
-            gap = gaps.pop().unwrap();
-            file.length -= 1;
-            file.id
-        } else {
-            end_file.length -= 1;
-            end_file.id
-        }
-    })
+fn get_block_ids(files: Vec<File>, gaps: Vec<Gap>) -> impl Iterator<Item = usize> {
+    let gap_lengths = gaps.iter().map(|gap| gap.length).chain(iter::repeat(0));
+    let mut blocks = Vec::new();
+    for (file, gap_length) in files.iter().zip(gap_lengths) {
+        blocks.extend(iter::repeat_n(Some(file.id), file.length as usize));
+        blocks.extend(iter::repeat_n(None, gap_length as usize));
+    }
+
+    // Each gap before the end of the compacted disk takes the next block from
+    // the end, and there are exactly as many of those as there are gaps.
+    let final_length = blocks.iter().flatten().count();
+    let mut moved = blocks[final_length..].iter().rev().flatten().copied();
+    let ids: Vec<usize> = blocks[..final_length]
+        .iter()
+        .filter_map(|block| block.or_else(|| moved.next()))
+        .collect();
+    ids.into_iter()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Data { files, gaps } = parse(buf);
+fn part1(data: &Data) -> Result<Answer> {
+    let Data { files, gaps } = data;
 
-    let result: usize = get_block_ids(files, gaps)
+    let result: usize = get_block_ids(files.clone(), gaps.clone())
         .enumerate()
         .map(|(i, id)| i * id)
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 fn get_block_ids_defrag(files: Vec<File>, gaps: Vec<Gap>) -> Vec<FileGap> {
@@ -109,16 +105,14 @@ fn get_block_ids_defrag(files: Vec<File>, gaps: Vec<Gap>) -> Vec<FileGap> {
         })
         .collect();
 
-    let max_id = file_gaps.iter().map(|f| f.id).max().unwrap();
-
     // eprintln!("{:?}", &file_gaps);
 
-    for id in (0..=max_id).rev() {
-        let (file_index, moving_file) = file_gaps
-            .iter()
-            .enumerate()
-            .find(|(_, f)| f.id == id)
-            .unwrap();
+    for id in (0..file_gaps.len()).rev() {
+        let Some((file_index, moving_file)) =
+            file_gaps.iter().enumerate().find(|(_, f)| f.id == id)
+        else {
+            continue;
+        };
         let place_after = file_gaps[0..file_index]
             .iter()
             .enumerate()
@@ -140,23 +134,25 @@ fn get_block_ids_defrag(files: Vec<File>, gaps: Vec<Gap>) -> Vec<FileGap> {
     file_gaps
 }
 
-fn part2(buf: &mut dyn Read) {
-    let Data { files, gaps } = parse(buf);
+fn part2(data: &Data) -> Result<Answer> {
+    let Data { files, gaps } = data;
 
-    let final_files_and_gaps = get_block_ids_defrag(files, gaps);
+    let final_files_and_gaps = get_block_ids_defrag(files.clone(), gaps.clone());
 
     let mut offset = 0;
     let result = final_files_and_gaps
         .into_iter()
         .map(|file_gap| {
-            dbg!(&file_gap);
-            let value = sum_between(offset, offset + file_gap.length as usize - 1) * file_gap.id;
+            let value = match file_gap.length {
+                0 => 0,
+                length => sum_between(offset, offset + length as usize - 1) * file_gap.id,
+            };
             offset += (file_gap.length + file_gap.gap_length) as usize;
             value
         })
         .sum::<usize>();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
 fn sum_to(n: usize) -> usize {
@@ -171,12 +167,28 @@ fn sum_between(a: usize, b: usize) -> usize {
     }
 }
 
-inventory::submit!(Aoc::new(2024, 9, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 9, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_small_disks() {
+        assert!(parse(&mut "1x\n".as_bytes()).is_err());
+
+        for disk_map in ["", "1\n", "21\n"] {
+            let data = parse(&mut disk_map.as_bytes()).unwrap();
+            assert_eq!(part1(&data).unwrap(), Answer::Number(0));
+            assert_eq!(part2(&data).unwrap(), Answer::Number(0));
+        }
+
+        // An empty file at the end leaves nothing to move into the gap.
+        let data = parse(&mut "110\n".as_bytes()).unwrap();
+        assert_eq!(part1(&data).unwrap(), Answer::Number(0));
+        assert_eq!(part2(&data).unwrap(), Answer::Number(0));
+    }
+
     #[test]
     fn test_get_block_ids_a() {
         let files: Vec<_> = [1, 3, 5]
@@ -289,11 +301,6 @@ mod tests {
             file_and_gaps
                 .into_iter()
                 .map(|file_gap| {
-                    dbg!(
-                        offset,
-                        offset + file_gap.length as usize - 1,
-                        sum_between(offset, offset + file_gap.length as usize - 1)
-                    );
                     let value =
                         sum_between(offset, offset + file_gap.length as usize - 1) * file_gap.id;
                     offset += (file_gap.length + file_gap.gap_length) as usize;
@@ -5,18 +5,26 @@ use std::{
 
 use grid::{Grid, Location};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Grid<char> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<char>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i > 0 && line.len() != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length,
+                line.len()
+            ))
+            .at_line(i + 1));
+        }
         line_length = line.len();
         lines.push(line);
     }
-    Grid::new(lines.join("").chars(), line_length)
+    Ok(Grid::new(lines.join("").chars(), line_length))
 }
 
 fn find_trailheads(grid: &Grid<char>) -> impl Iterator<Item = Location> + '_ {
@@ -68,32 +76,30 @@ fn find_reachable_peaks(grid: &Grid<char>, location: &Location) -> Vec<Location>
         .collect()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let grid = parse(buf);
-    let trailheads = find_trailheads(&grid);
+fn part1(grid: &Grid<char>) -> Result<Answer> {
+    let trailheads = find_trailheads(grid);
     let result: usize = trailheads
         .map(|head| {
-            find_reachable_peaks(&grid, &head)
+            find_reachable_peaks(grid, &head)
                 .into_iter()
                 .collect::<HashSet<_>>()
                 .len()
         })
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let grid = parse(buf);
-    let trailheads = find_trailheads(&grid);
+fn part2(grid: &Grid<char>) -> Result<Answer> {
+    let trailheads = find_trailheads(grid);
     let result: usize = trailheads
-        .map(|head| find_reachable_peaks(&grid, &head).len())
+        .map(|head| find_reachable_peaks(grid, &head).len())
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 10, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 10, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -3,13 +3,16 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<usize> {
+fn parse(buf: &mut dyn Read) -> Result<Vec<usize>> {
     let mut reader = BufReader::new(buf);
     let mut line: String = String::new();
-    reader.read_line(&mut line).unwrap();
-    line.split(' ').map(|v| v.trim().parse().unwrap()).collect()
+    reader.read_line(&mut line)?;
+    line.split(' ')
+        .map(|v| v.trim().parse())
+        .collect::<std::result::Result<_, _>>()
+        .at_line(1)
 }
 
 fn blink(number: usize) -> Vec<usize> {
@@ -30,9 +33,7 @@ fn blink(number: usize) -> Vec<usize> {
 }
 
 fn blink_n(mut numbers: Vec<usize>, n: usize) -> Vec<usize> {
-    for i in 0..n {
-        eprintln!("Step {}", i);
-
+    for _ in 0..n {
         let new_numbers: Vec<_> = numbers.into_iter().flat_map(blink).collect();
         numbers = new_numbers;
     }
@@ -40,13 +41,10 @@ fn blink_n(mut numbers: Vec<usize>, n: usize) -> Vec<usize> {
     numbers
 }
 
-fn part1(buf: &mut dyn Read) {
-    let numbers = parse(buf);
-    dbg!(&numbers);
-
-    let numbers = blink_n(numbers, 25);
+fn part1(numbers: &[usize]) -> Result<Answer> {
+    let numbers = blink_n(numbers.to_vec(), 25);
 
-    println!("Part 1: {}", numbers.len());
+    Ok(numbers.len().into())
 }
 
 type Counter<T> = HashMap<T, usize>;
@@ -74,10 +72,9 @@ where
     counter
 }
 
-fn blink_counter_n(numbers: Vec<usize>, n: usize) -> Counter<usize> {
-    let mut counter = list_to_counter(numbers.into_iter());
-    for i in 0..n {
-        dbg!(i);
+fn blink_counter_n(numbers: &[usize], n: usize) -> Counter<usize> {
+    let mut counter = list_to_counter(numbers.iter().copied());
+    for _ in 0..n {
         let new_counter = counter
             .into_iter()
             .map(|(k, multiplier)| {
@@ -99,15 +96,13 @@ fn blink_counter_n(numbers: Vec<usize>, n: usize) -> Counter<usize> {
     counter
 }
 
-fn part2(buf: &mut dyn Read) {
-    let numbers = parse(buf);
-
+fn part2(numbers: &[usize]) -> Result<Answer> {
     let result = blink_counter_n(numbers, 75).into_values().sum::<usize>();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 11, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 11, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -149,29 +144,29 @@ mod tests {
     #[test]
     fn test_blink_counter_n() {
         assert_eq!(
-            blink_counter_n(vec![125, 17], 1),
+            blink_counter_n(&[125, 17], 1),
             list_to_counter(vec![253000, 1, 7].into_iter())
         );
         assert_eq!(
-            blink_counter_n(vec![125, 17], 2),
+            blink_counter_n(&[125, 17], 2),
             list_to_counter(vec![253, 0, 2024, 14168].into_iter())
         );
         assert_eq!(
-            blink_counter_n(vec![125, 17], 3),
+            blink_counter_n(&[125, 17], 3),
             list_to_counter(vec![512072, 1, 20, 24, 28676032].into_iter())
         );
         assert_eq!(
-            blink_counter_n(vec![125, 17], 4),
+            blink_counter_n(&[125, 17], 4),
             list_to_counter(vec![512, 72, 2024, 2, 0, 2, 4, 2867, 6032,].into_iter())
         );
         assert_eq!(
-            blink_counter_n(vec![125, 17], 5),
+            blink_counter_n(&[125, 17], 5),
             list_to_counter(
                 vec![1036288, 7, 2, 20, 24, 4048, 1, 4048, 8096, 28, 67, 60, 32].into_iter()
             )
         );
         assert_eq!(
-            blink_counter_n(vec![125, 17], 6),
+            blink_counter_n(&[125, 17], 6),
             HashMap::from([
                 (2097446912, 1),
                 (14168, 1),
@@ -191,25 +186,16 @@ mod tests {
             ])
         );
         assert_eq!(
-            blink_counter_n(vec![125, 17], 6)
-                .into_values()
-                .sum::<usize>(),
+            blink_counter_n(&[125, 17], 6).into_values().sum::<usize>(),
             22
         );
         for n in 9..25 {
-            let a = blink_counter_n(vec![125, 17], n);
+            let a = blink_counter_n(&[125, 17], n);
             let b = list_to_counter(blink_n(vec![125, 17], n).into_iter());
-            for k in a.keys().chain(b.keys()) {
-                if a[k] != b[k] {
-                    dbg!((k, a[k], b[k]));
-                }
-            }
             assert_eq!(a, b);
         }
         assert_eq!(
-            blink_counter_n(vec![125, 17], 25)
-                .into_values()
-                .sum::<usize>(),
+            blink_counter_n(&[125, 17], 25).into_values().sum::<usize>(),
             55312
         );
     }
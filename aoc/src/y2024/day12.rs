@@ -5,18 +5,26 @@ use std::{
 
 use grid::{Grid, Location};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Grid<char> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<char>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i > 0 && line.len() != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length,
+                line.len()
+            ))
+            .at_line(i + 1));
+        }
         line_length = line.len();
         lines.push(line);
     }
-    Grid::new(lines.join("").chars(), line_length)
+    Ok(Grid::new(lines.join("").chars(), line_length))
 }
 
 fn find_plots(garden: &Grid<char>) -> Vec<Vec<Location>> {
@@ -145,10 +153,8 @@ fn find_area(plot: Vec<Location>) -> usize {
     plot.len()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let garden = parse(buf);
-
-    let plots = find_plots(&garden);
+fn part1(garden: &Grid<char>) -> Result<Answer> {
+    let plots = find_plots(garden);
     let result: usize = plots
         .into_iter()
         .map(|plot| {
@@ -158,24 +164,22 @@ fn part1(buf: &mut dyn Read) {
         })
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let garden = parse(buf);
-
-    let plots = find_plots(&garden);
+fn part2(garden: &Grid<char>) -> Result<Answer> {
+    let plots = find_plots(garden);
 
     let result: usize = plots
         .into_iter()
         .map(|plot| {
             let area = find_area(plot.clone());
-            let corners = find_corners(plot, &garden);
+            let corners = find_corners(plot, garden);
             area * corners
         })
         .sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 12, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 12, parse, part1, part2,));
@@ -1,8 +1,12 @@
-use std::io::{BufRead, BufReader, Read};
+use std::{
+    io::{BufRead, BufReader, Read},
+    num::ParseIntError,
+    str::FromStr,
+};
 
 use grid::{Location, Offset};
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 #[derive(Debug, Clone)]
 struct Machine {
@@ -11,7 +15,28 @@ struct Machine {
     prize_position: Location,
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<Machine> {
+fn parse_pair<T>(data: &str, x_prefix: &str, y_prefix: &str) -> Result<(T, T)>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    let (x, y) = data
+        .trim()
+        .split_once(',')
+        .ok_or_else(|| Error::parse("expected an X and Y value"))?;
+    let x = x
+        .trim()
+        .strip_prefix(x_prefix)
+        .ok_or_else(|| Error::parse(format!("expected '{}'", x_prefix)))?
+        .parse()?;
+    let y = y
+        .trim()
+        .strip_prefix(y_prefix)
+        .ok_or_else(|| Error::parse(format!("expected '{}'", y_prefix)))?
+        .parse()?;
+    Ok((x, y))
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Machine>> {
     let reader = BufReader::new(buf);
 
     let mut machines = Vec::new();
@@ -20,39 +45,37 @@ fn parse(buf: &mut dyn Read) -> Vec<Machine> {
         button_b: Offset { x: 0, y: 0 },
         prize_position: Location { x: 0, y: 0 },
     };
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         if line.is_empty() {
             machines.push(machine.clone());
             continue;
         }
-        let (ind, data) = line.split_once(':').unwrap();
+        let (ind, data) = line
+            .split_once(':')
+            .ok_or_else(|| Error::parse("expected '<label>: <values>'").at_line(i + 1))?;
         match ind {
             "Button A" => {
-                let (x, y) = data.trim().split_once(',').unwrap();
-                let x = x.trim().strip_prefix('X').unwrap().parse().unwrap();
-                let y = y.trim().strip_prefix('Y').unwrap().parse().unwrap();
+                let (x, y) = parse_pair(data, "X", "Y").at_line(i + 1)?;
                 machine.button_a = Offset { x, y };
             }
             "Button B" => {
-                let (x, y) = data.trim().split_once(',').unwrap();
-                let x = x.trim().strip_prefix('X').unwrap().parse().unwrap();
-                let y = y.trim().strip_prefix('Y').unwrap().parse().unwrap();
+                let (x, y) = parse_pair(data, "X", "Y").at_line(i + 1)?;
                 machine.button_b = Offset { x, y };
             }
             "Prize" => {
-                let (x, y) = data.trim().split_once(',').unwrap();
-                let x = x.trim().strip_prefix("X=").unwrap().parse().unwrap();
-                let y = y.trim().strip_prefix("Y=").unwrap().parse().unwrap();
+                let (x, y) = parse_pair(data, "X=", "Y=").at_line(i + 1)?;
                 machine.prize_position = Location { x, y };
             }
-            _ => {}
+            _ => {
+                return Err(Error::parse(format!("unknown label '{}'", ind)).at_line(i + 1));
+            }
         }
     }
 
     machines.push(machine);
 
-    machines
+    Ok(machines)
 }
 
 // X = a.x * A + b.x * B
@@ -91,38 +114,34 @@ fn get_button_presses(machine: &Machine) -> Option<(usize, usize)> {
     }
 }
 
-fn part1(buf: &mut dyn Read) {
-    let machines = parse(buf);
-
+fn part1(machines: &[Machine]) -> Result<Answer> {
     let result: usize = machines
         .iter()
         .filter_map(get_button_presses)
         .map(|(a, b)| a * 3 + b)
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let machines = parse(buf);
-
+fn part2(machines: &[Machine]) -> Result<Answer> {
     let result: usize = machines
-        .into_iter()
+        .iter()
         .map(|machine| Machine {
             prize_position: Location {
                 x: machine.prize_position.x + 10000000000000,
                 y: machine.prize_position.y + 10000000000000,
             },
-            ..machine
+            ..machine.clone()
         })
         .filter_map(|machine| get_button_presses(&machine))
         .map(|(a, b)| a * 3 + b)
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 13, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 13, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
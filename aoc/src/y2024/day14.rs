@@ -2,7 +2,7 @@ use std::io::{BufRead, BufReader, Read};
 
 use grid::{Grid, Location, Offset};
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 #[derive(Debug, PartialEq)]
 struct Robot {
@@ -10,36 +10,35 @@ struct Robot {
     velocity: Offset,
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<Robot> {
+fn parse_pair(value: &str, prefix: &str) -> Result<(isize, isize)> {
+    let (x, y) = value
+        .strip_prefix(prefix)
+        .and_then(|value| value.split_once(','))
+        .ok_or_else(|| Error::parse(format!("expected '{}<x>,<y>'", prefix)))?;
+    Ok((x.parse()?, y.parse()?))
+}
+
+fn parse_robot(line: &str) -> Result<Robot> {
+    let (position, velocity) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::parse("expected a position and a velocity"))?;
+    let (x, y) = parse_pair(position, "p=")?;
+    let position = Location {
+        x: usize::try_from(x).map_err(|_| Error::parse("position must not be negative"))?,
+        y: usize::try_from(y).map_err(|_| Error::parse("position must not be negative"))?,
+    };
+    let (x, y) = parse_pair(velocity, "v=")?;
+    let velocity = Offset { x, y };
+
+    Ok(Robot { position, velocity })
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Robot>> {
     let reader = BufReader::new(buf);
     reader
         .lines()
-        .map_while(|line| {
-            let Ok(line) = line else { return None };
-
-            let (position, velocity) = line.split_once(' ').unwrap();
-            let mut position = position
-                .strip_prefix("p=")
-                .unwrap()
-                .split(',')
-                .map(|v| v.parse::<usize>().unwrap());
-            let position = Location {
-                x: position.next().unwrap(),
-                y: position.next().unwrap(),
-            };
-
-            let mut velocity = velocity
-                .strip_prefix("v=")
-                .unwrap()
-                .split(',')
-                .map(|v| v.parse::<isize>().unwrap());
-            let velocity = Offset {
-                x: velocity.next().unwrap(),
-                y: velocity.next().unwrap(),
-            };
-
-            Some(Robot { position, velocity })
-        })
+        .enumerate()
+        .map(|(i, line)| parse_robot(&line?).at_line(i + 1))
         .collect()
 }
 
@@ -59,9 +58,7 @@ fn simulate_movement(robot: &Robot, t: isize, width: usize, height: usize) -> Ro
     }
 }
 
-fn part1(buf: &mut dyn Read) {
-    let robots = parse(buf);
-
+fn part1(robots: &[Robot]) -> Result<Answer> {
     let width = 101;
     let height = 103;
 
@@ -87,12 +84,10 @@ fn part1(buf: &mut dyn Read) {
 
     let result: usize = counts.into_iter().product();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let robots = parse(buf);
-
+fn part2(robots: &[Robot]) -> Result<Answer> {
     let width = 101;
     let height = 103;
 
@@ -108,14 +103,14 @@ fn part2(buf: &mut dyn Read) {
         let cells: String = grid.cells().iter().cloned().collect();
         // The image contains some lines. 10 'O's should be enough
         if cells.contains("OOOOOOOOO") {
-            eprintln!("{}", grid);
-            println!("Part 2: {}", i);
-            return;
+            return Ok(i.into());
         }
     }
+
+    Err(Error::no_solution("the robots never form a picture"))
 }
 
-inventory::submit!(Aoc::new(2024, 14, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 14, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
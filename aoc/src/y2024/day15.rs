@@ -1,11 +1,11 @@
 use std::{
     fmt::Display,
-    io::{BufRead, BufReader, Read},
+    io::{self, BufRead, BufReader, Read},
 };
 
 use grid::{Direction, Grid, Location};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Cell {
@@ -27,52 +27,50 @@ impl Display for Cell {
     }
 }
 
-fn parse_map(reader: &mut dyn BufRead) -> Grid<Cell> {
+fn parse_map(lines: &mut impl Iterator<Item = (usize, io::Result<String>)>) -> Result<Grid<Cell>> {
+    let mut cells = Vec::new();
     let mut line_length = 0;
-    let lines = reader.lines().map_while(|line| {
-        let line = line.unwrap();
+    for (i, line) in lines {
+        let line = line?;
         if line.is_empty() {
-            None
-        } else {
-            line_length = line.len();
-            Some(line)
+            break;
         }
-    });
-    Grid::new(
-        lines.collect::<String>().chars().map(|c| match c {
-            '.' => Cell::Empty,
-            '@' => Cell::Robot,
-            'O' => Cell::Box,
-            '#' => Cell::Wall,
-            _ => panic!("Invalid map cell: {}", c),
-        }),
-        line_length,
-    )
+        line_length = line.len();
+        for (j, c) in line.chars().enumerate() {
+            let cell = match c {
+                '.' => Cell::Empty,
+                '@' => Cell::Robot,
+                'O' => Cell::Box,
+                '#' => Cell::Wall,
+                _ => return Err(Error::parse(format!("invalid map cell '{}'", c)).at(i + 1, j + 1)),
+            };
+            cells.push(cell);
+        }
+    }
+    Ok(Grid::new(cells, line_length))
 }
 
-fn parse_moves(reader: &mut dyn BufRead) -> Vec<Direction> {
-    let moves_str: String = reader
-        .lines()
-        .map_while(|line| {
-            let line = line.unwrap();
-            if line.is_empty() {
-                None
-            } else {
-                Some(line)
-            }
-        })
-        .collect();
-
-    moves_str
-        .chars()
-        .map(|c| match c {
-            '^' => Direction::Up,
-            '>' => Direction::Right,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            _ => panic!("Invalid direction char: {}", c),
-        })
-        .collect()
+fn parse_moves(
+    lines: &mut impl Iterator<Item = (usize, io::Result<String>)>,
+) -> Result<Vec<Direction>> {
+    let mut moves = Vec::new();
+    for (i, line) in lines {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        for (j, c) in line.chars().enumerate() {
+            let direction = match c {
+                '^' => Direction::Up,
+                '>' => Direction::Right,
+                'v' => Direction::Down,
+                '<' => Direction::Left,
+                _ => return Err(Error::parse(format!("invalid move '{}'", c)).at(i + 1, j + 1)),
+            };
+            moves.push(direction);
+        }
+    }
+    Ok(moves)
 }
 
 struct Data {
@@ -80,13 +78,14 @@ struct Data {
     moves: Vec<Direction>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
-    let mut reader: BufReader<_> = BufReader::new(buf);
+fn parse(buf: &mut dyn Read) -> Result<Data> {
+    let reader = BufReader::new(buf);
+    let mut lines = reader.lines().enumerate();
 
-    let map = parse_map(&mut reader);
-    let moves = parse_moves(&mut reader);
+    let map = parse_map(&mut lines)?;
+    let moves = parse_moves(&mut lines)?;
 
-    Data { map, moves }
+    Ok(Data { map, moves })
 }
 
 fn perform_move(map: &mut Grid<Cell>, cell_location: &Location, direction: Direction) -> bool {
@@ -113,14 +112,15 @@ fn perform_move(map: &mut Grid<Cell>, cell_location: &Location, direction: Direc
     }
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Data { mut map, moves } = parse(buf);
+fn part1(data: &Data) -> Result<Answer> {
+    let Data { map, moves } = data;
+    let mut map = map.clone();
 
     let mut robot_location = map
         .iter_locations()
         .find(|location| *map.get(location).unwrap() == Cell::Robot)
-        .unwrap();
-    for direction in moves {
+        .ok_or_else(|| Error::parse("no robot '@' in the map"))?;
+    for &direction in moves {
         let did_move = perform_move(&mut map, &robot_location, direction);
         if did_move {
             robot_location = robot_location.to(direction).unwrap();
@@ -138,7 +138,7 @@ fn part1(buf: &mut dyn Read) {
         })
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -239,24 +239,21 @@ fn perform_wide_move(
     }
 }
 
-fn part2(buf: &mut dyn Read) {
-    let Data { map, moves } = parse(buf);
-    let mut map = stretch_map(map);
+fn part2(data: &Data) -> Result<Answer> {
+    let Data { map, moves } = data;
+    let mut map = stretch_map(map.clone());
 
     let mut robot_location = map
         .iter_locations()
         .find(|location| *map.get(location).unwrap() == WideCell::Robot)
-        .unwrap();
-    for direction in moves {
+        .ok_or_else(|| Error::parse("no robot '@' in the map"))?;
+    for &direction in moves {
         let did_move = perform_wide_move(&mut map, &robot_location, direction, false, true);
         if did_move {
             robot_location = robot_location.to(direction).unwrap();
         }
     }
 
-    eprintln!("--- Final Map ---");
-    eprintln!("{}", map);
-
     let result: usize = map
         .iter_locations()
         .filter_map(|location| {
@@ -267,7 +264,7 @@ fn part2(buf: &mut dyn Read) {
             }
         })
         .sum();
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 15, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 15, parse, part1, part2,));
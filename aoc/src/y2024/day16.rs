@@ -8,7 +8,7 @@ use std::{
 
 use grid::{Direction, Grid, Location};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Cell {
@@ -30,28 +30,34 @@ impl Display for Cell {
     }
 }
 
-fn parse(buf: &mut dyn Read) -> Grid<Cell> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<Cell>> {
     let reader = BufReader::new(buf);
+    let mut cells = Vec::new();
     let mut line_length = 0;
-    let lines = reader.lines().map_while(|line| {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         if line.is_empty() {
-            None
-        } else {
-            line_length = line.len();
-            Some(line)
+            break;
+        }
+        line_length = line.len();
+        for (j, c) in line.chars().enumerate() {
+            let cell = match c {
+                '.' => Cell::Empty,
+                'S' => Cell::Start,
+                'E' => Cell::End,
+                '#' => Cell::Wall,
+                _ => return Err(Error::parse(format!("invalid map cell '{}'", c)).at(i + 1, j + 1)),
+            };
+            cells.push(cell);
         }
-    });
-    Grid::new(
-        lines.collect::<String>().chars().map(|c| match c {
-            '.' => Cell::Empty,
-            'S' => Cell::Start,
-            'E' => Cell::End,
-            '#' => Cell::Wall,
-            _ => panic!("Invalid map cell: {}", c),
-        }),
-        line_length,
-    )
+    }
+    Ok(Grid::new(cells, line_length))
+}
+
+fn find_cell(map: &Grid<Cell>, cell: Cell) -> Result<Location> {
+    map.iter_locations()
+        .find(|location| map.get(location) == Some(&cell))
+        .ok_or_else(|| Error::parse(format!("no '{}' in the map", cell.to_string().trim())))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -281,37 +287,26 @@ fn find_routes(map: &Grid<Cell>, start: &Location, end: &Location) -> Vec<Route>
     shortest_routes
 }
 
-fn part1(buf: &mut dyn Read) {
-    let map = parse(buf);
-
-    let start_location = map
-        .iter_locations()
-        .find(|location| map.get(location) == Some(&Cell::Start))
-        .unwrap();
-    let end_location = map
-        .iter_locations()
-        .find(|location| map.get(location) == Some(&Cell::End))
-        .unwrap();
-
-    let routes = find_routes(&map, &start_location, &end_location);
-    let route = routes.first();
-    let result = route.unwrap().cost();
-    println!("Part 1: {}", result);
-}
+fn part1(map: &Grid<Cell>) -> Result<Answer> {
+    let start_location = find_cell(map, Cell::Start)?;
+    let end_location = find_cell(map, Cell::End)?;
 
-fn part2(buf: &mut dyn Read) {
-    let map = parse(buf);
+    let routes = find_routes(map, &start_location, &end_location);
+    let route = routes
+        .first()
+        .ok_or_else(|| Error::no_solution("no route from the start to the end"))?;
+    let result = route.cost();
+    Ok(result.into())
+}
 
-    let start_location = map
-        .iter_locations()
-        .find(|location| map.get(location) == Some(&Cell::Start))
-        .unwrap();
-    let end_location = map
-        .iter_locations()
-        .find(|location| map.get(location) == Some(&Cell::End))
-        .unwrap();
+fn part2(map: &Grid<Cell>) -> Result<Answer> {
+    let start_location = find_cell(map, Cell::Start)?;
+    let end_location = find_cell(map, Cell::End)?;
 
-    let routes = find_routes(&map, &start_location, &end_location);
+    let routes = find_routes(map, &start_location, &end_location);
+    if routes.is_empty() {
+        return Err(Error::no_solution("no route from the start to the end"));
+    }
 
     let mut cells = HashSet::new();
     for route in routes {
@@ -321,7 +316,7 @@ fn part2(buf: &mut dyn Read) {
     }
 
     let result = cells.len();
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 16, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 16, parse, part1, part2,));
@@ -1,6 +1,6 @@
 use std::io::{BufRead, BufReader, Read};
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 #[derive(Debug, Clone, PartialEq)]
 struct Registers {
@@ -19,13 +19,13 @@ enum Operand {
 }
 
 impl Operand {
-    fn parse_combo(operand: u8) -> Self {
+    fn parse_combo(operand: u8) -> Result<Self> {
         match operand {
-            0..=3 => Self::Literal(operand),
-            4 => Self::RegisterA,
-            5 => Self::RegisterB,
-            6 => Self::RegisterC,
-            _ => panic!("Invalid Combo Operand: {}", operand),
+            0..=3 => Ok(Self::Literal(operand)),
+            4 => Ok(Self::RegisterA),
+            5 => Ok(Self::RegisterB),
+            6 => Ok(Self::RegisterC),
+            _ => Err(Error::parse(format!("invalid combo operand {}", operand))),
         }
     }
 
@@ -56,17 +56,17 @@ enum Operator {
 }
 
 impl Operator {
-    fn parse(instruction: u8, operand: u8) -> Result<Self, ()> {
+    fn parse(instruction: u8, operand: u8) -> Result<Self> {
         match instruction {
-            0 => Ok(Self::Divide(Operand::parse_combo(operand))),
+            0 => Ok(Self::Divide(Operand::parse_combo(operand)?)),
             1 => Ok(Self::BitwiseXorLiteral(Operand::literal(operand))),
-            2 => Ok(Self::StoreB(Operand::parse_combo(operand))),
+            2 => Ok(Self::StoreB(Operand::parse_combo(operand)?)),
             3 => Ok(Self::JumpNotZero(Operand::literal(operand))),
             4 => Ok(Self::BitwiseXorC),
-            5 => Ok(Self::Output(Operand::parse_combo(operand))),
-            6 => Ok(Self::DivideB(Operand::parse_combo(operand))),
-            7 => Ok(Self::DivideC(Operand::parse_combo(operand))),
-            _ => Err(()),
+            5 => Ok(Self::Output(Operand::parse_combo(operand)?)),
+            6 => Ok(Self::DivideB(Operand::parse_combo(operand)?)),
+            7 => Ok(Self::DivideC(Operand::parse_combo(operand)?)),
+            _ => Err(Error::parse(format!("invalid instruction {}", instruction))),
         }
     }
 
@@ -121,7 +121,7 @@ struct Data {
     instructions: Vec<u8>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
+fn parse(buf: &mut dyn Read) -> Result<Data> {
     let reader = BufReader::new(buf);
 
     let mut registers = Registers {
@@ -132,45 +132,51 @@ fn parse(buf: &mut dyn Read) -> Data {
     };
     let mut instructions: Vec<u8> = Vec::new();
 
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         let Some((label, data)) = line.split_once(':') else {
             continue;
         };
         let data = data.trim();
         match label {
             "Register A" => {
-                registers.a = data.parse().unwrap();
+                registers.a = data.parse().at_line(i + 1)?;
             }
             "Register B" => {
-                registers.b = data.parse().unwrap();
+                registers.b = data.parse().at_line(i + 1)?;
             }
             "Register C" => {
-                registers.c = data.parse().unwrap();
+                registers.c = data.parse().at_line(i + 1)?;
+            }
+            "Program" => {
+                for value in data.split(',') {
+                    instructions.push(value.parse().at_line(i + 1)?);
+                }
             }
-            "Program" => instructions.extend(data.split(',').map(|v| v.parse::<u8>().unwrap())),
             _ => {
-                panic!("Bleh")
+                return Err(Error::parse(format!("unknown label '{}'", label)).at_line(i + 1));
             }
         }
     }
 
-    Data {
+    Ok(Data {
         registers,
         instructions,
-    }
+    })
 }
 
-fn run_step(registers: &mut Registers, instructions: &[u8]) -> Result<Option<u8>, ()> {
+fn run_step(registers: &mut Registers, instructions: &[u8]) -> Result<Option<u8>> {
     let instruction = instructions[registers.instruction_pointer];
-    let operand = instructions[registers.instruction_pointer + 1];
+    let operand = *instructions
+        .get(registers.instruction_pointer + 1)
+        .ok_or_else(|| Error::parse("the program ends without an operand"))?;
     registers.instruction_pointer += 2;
 
     let operator = Operator::parse(instruction, operand);
     Ok(operator?.perform(registers))
 }
 
-fn run_program(registers: &mut Registers, instructions: &[u8]) -> Result<Vec<u8>, ()> {
+fn run_program(registers: &mut Registers, instructions: &[u8]) -> Result<Vec<u8>> {
     let max_instruction_pointer = instructions.len();
     let mut outputs = Vec::new();
     while registers.instruction_pointer < max_instruction_pointer {
@@ -182,17 +188,17 @@ fn run_program(registers: &mut Registers, instructions: &[u8]) -> Result<Vec<u8>
     Ok(outputs)
 }
 
-fn part1(buf: &mut dyn Read) {
+fn part1(data: &Data) -> Result<Answer> {
     let Data {
-        mut registers,
+        registers,
         instructions,
-    } = parse(buf);
+    } = data;
 
-    let outputs = run_program(&mut registers, &instructions).unwrap();
+    let outputs = run_program(&mut registers.clone(), instructions)?;
 
     let results: Vec<String> = outputs.iter().map(|v| v.to_string()).collect();
     let result = results.join(",");
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 fn find_number(
@@ -200,7 +206,7 @@ fn find_number(
     instructions: &[u8],
     position: usize,
     start_value: usize,
-) -> Option<usize> {
+) -> Result<Option<usize>> {
     for i in 0..8 {
         let value = start_value + i;
         let output = run_program(
@@ -209,38 +215,38 @@ fn find_number(
                 ..registers.clone()
             },
             instructions,
-        )
-        .unwrap();
+        )?;
 
         if output.len() > position
             && output[output.len() - position - 1]
                 == instructions[instructions.len() - position - 1]
         {
             if (position + 1) == instructions.len() {
-                return Some(value);
+                return Ok(Some(value));
             } else {
-                let Some(result) = find_number(registers, instructions, position + 1, value * 8)
+                let Some(result) = find_number(registers, instructions, position + 1, value * 8)?
                 else {
                     continue;
                 };
-                return Some(result);
+                return Ok(Some(result));
             }
         }
     }
-    None
+    Ok(None)
 }
 
-fn part2(buf: &mut dyn Read) {
+fn part2(data: &Data) -> Result<Answer> {
     let Data {
         registers,
         instructions,
-    } = parse(buf);
+    } = data;
 
-    let result = find_number(&registers, &instructions, 0, 0).unwrap();
-    println!("Part 2: {}", result);
+    let result = find_number(registers, instructions, 0, 0)?
+        .ok_or_else(|| Error::no_solution("no value of A makes the program output itself"))?;
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 17, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 17, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -6,20 +6,24 @@ use std::{
 use grid::{Direction, Grid, Location};
 use priority_queue::PriorityQueue;
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<Location> {
+fn parse_location(line: &str) -> Result<Location> {
+    let (x, y) = line
+        .split_once(',')
+        .ok_or_else(|| Error::parse("expected '<x>,<y>'"))?;
+    Ok(Location {
+        x: x.parse()?,
+        y: y.parse()?,
+    })
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Location>> {
     let reader = BufReader::new(buf);
     reader
         .lines()
-        .map_while(|line| {
-            let line = line.ok()?;
-            let (x, y) = line.split_once(',')?;
-            Some(Location {
-                x: x.parse().ok()?,
-                y: y.parse().ok()?,
-            })
-        })
+        .enumerate()
+        .map(|(i, line)| parse_location(&line?).at_line(i + 1))
         .collect()
 }
 
@@ -72,12 +76,10 @@ fn solve_maze<T>(
     None
 }
 
-fn part1(buf: &mut dyn Read) {
-    let corruption_positions = parse(buf);
-
+fn part1(corruption_positions: &[Location]) -> Result<Answer> {
     let mut grid = Grid::new([' '].into_iter().cycle().take(71 * 71), 71);
-    for corruption_position in corruption_positions.into_iter().take(1024) {
-        grid.set(&corruption_position, '#');
+    for corruption_position in corruption_positions.iter().take(1024) {
+        grid.set(corruption_position, '#');
     }
 
     let route = solve_maze(
@@ -85,18 +87,17 @@ fn part1(buf: &mut dyn Read) {
         |location, grid| grid.get(location).map(|cell| *cell != '#').unwrap_or(false),
         &Location { x: 0, y: 0 },
         &Location { x: 70, y: 70 },
-    );
+    )
+    .ok_or_else(|| Error::no_solution("the exit is blocked after 1024 bytes"))?;
 
-    for pos in route.as_ref().unwrap() {
+    for pos in &route {
         grid.set(pos, 'O');
     }
-    let result = route.unwrap().len() - 1;
-    println!("Part 1: {}", result);
+    let result = route.len() - 1;
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let corruption_positions = parse(buf);
-
+fn part2(corruption_positions: &[Location]) -> Result<Answer> {
     let mut grid = Grid::new([' '].into_iter().cycle().take(71 * 71), 71);
     let mut route = solve_maze(
         &grid,
@@ -106,8 +107,8 @@ fn part2(buf: &mut dyn Read) {
     )
     .unwrap();
     for corruption_position in corruption_positions {
-        grid.set(&corruption_position, '#');
-        if route.contains(&corruption_position) {
+        grid.set(corruption_position, '#');
+        if route.contains(corruption_position) {
             // Recalculate route
             if let Some(solution) = solve_maze(
                 &grid,
@@ -117,14 +118,12 @@ fn part2(buf: &mut dyn Read) {
             ) {
                 route = solution
             } else {
-                println!(
-                    "Part 2: {},{}",
-                    corruption_position.x, corruption_position.y
-                );
-                return;
+                return Ok(format!("{},{}", corruption_position.x, corruption_position.y).into());
             }
         }
     }
+
+    Err(Error::no_solution("the exit is never blocked"))
 }
 
-inventory::submit!(Aoc::new(2024, 18, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 18, parse, part1, part2,));
@@ -5,38 +5,43 @@ use std::{
 
 use regex::Regex;
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
 struct Data {
     towels: Vec<String>,
     patterns: Vec<String>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
+fn parse(buf: &mut dyn Read) -> Result<Data> {
     let reader = BufReader::new(buf);
     let mut lines = reader.lines();
     let towels: Vec<_> = lines
         .next()
-        .unwrap()
-        .unwrap()
+        .ok_or_else(|| Error::parse("missing the list of towels").at_line(1))??
         .split(',')
         .map(str::trim)
         .map(|v| v.to_owned())
         .collect();
-    let patterns: Vec<_> = lines.skip(1).map_while(Result::ok).collect();
+    if let Some(towel) = towels
+        .iter()
+        .find(|towel| towel.is_empty() || !towel.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        return Err(Error::parse(format!("invalid towel '{}'", towel)).at_line(1));
+    }
+    let patterns = lines.skip(1).collect::<std::io::Result<Vec<_>>>()?;
 
-    Data { towels, patterns }
+    Ok(Data { towels, patterns })
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Data { towels, patterns } = parse(buf);
+fn part1(data: &Data) -> Result<Answer> {
+    let Data { towels, patterns } = data;
     let metapattern = Regex::new(&format!("^({})+$", towels.join("|"))).unwrap();
     let result = patterns
-        .into_iter()
+        .iter()
         .filter(|pattern| metapattern.is_match(pattern))
         .count();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 fn count_options(
@@ -72,15 +77,19 @@ fn count_options(
         .sum()
 }
 
-fn part2(buf: &mut dyn Read) {
-    let Data { towels, patterns } = parse(buf);
+fn part2(data: &Data) -> Result<Answer> {
+    let Data { towels, patterns } = data;
     let metapattern = Regex::new(&format!("^({})+$", towels.join("|"))).unwrap();
     let valid_patterns: Vec<_> = patterns
-        .into_iter()
+        .iter()
         .filter(|pattern| metapattern.is_match(pattern))
         .collect();
 
-    let max_towel_size = towels.iter().map(|t| t.len()).max().unwrap();
+    let max_towel_size = towels
+        .iter()
+        .map(|t| t.len())
+        .max()
+        .ok_or_else(|| Error::parse("there are no towels"))?;
     let mut towels_by_size: Vec<Vec<&str>> = vec![vec![]; max_towel_size + 1];
     towels.iter().for_each(|t| towels_by_size[t.len()].push(t));
 
@@ -89,7 +98,24 @@ fn part2(buf: &mut dyn Read) {
         .map(|pattern| count_options(pattern, &towels_by_size, &mut HashMap::new()))
         .sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 19, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 19, parse, part1, part2,));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_towels() {
+        assert!(parse(&mut "".as_bytes()).is_err());
+        assert!(parse(&mut "r, , b\n\nrb\n".as_bytes()).is_err());
+
+        let data = Data {
+            towels: vec![],
+            patterns: vec!["rb".to_string()],
+        };
+        assert!(part2(&data).is_err());
+    }
+}
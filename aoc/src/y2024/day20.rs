@@ -6,7 +6,7 @@ use std::{
 
 use grid::{Direction, Grid, Location};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Cell {
     Empty,
@@ -27,28 +27,34 @@ impl Display for Cell {
     }
 }
 
-fn parse(buf: &mut dyn Read) -> Grid<Cell> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<Cell>> {
     let reader = BufReader::new(buf);
+    let mut cells = Vec::new();
     let mut line_length = 0;
-    let lines = reader.lines().map_while(|line| {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         if line.is_empty() {
-            None
-        } else {
-            line_length = line.len();
-            Some(line)
+            break;
         }
-    });
-    Grid::new(
-        lines.collect::<String>().chars().map(|c| match c {
-            '.' => Cell::Empty,
-            'S' => Cell::Start,
-            'E' => Cell::End,
-            '#' => Cell::Wall,
-            _ => panic!("Invalid map cell: {}", c),
-        }),
-        line_length,
-    )
+        line_length = line.len();
+        for (j, c) in line.chars().enumerate() {
+            let cell = match c {
+                '.' => Cell::Empty,
+                'S' => Cell::Start,
+                'E' => Cell::End,
+                '#' => Cell::Wall,
+                _ => return Err(Error::parse(format!("invalid map cell '{}'", c)).at(i + 1, j + 1)),
+            };
+            cells.push(cell);
+        }
+    }
+    Ok(Grid::new(cells, line_length))
+}
+
+fn find_cell(maze: &Grid<Cell>, cell: Cell) -> Result<Location> {
+    maze.iter_locations()
+        .find(|v| maze.get(v) == Some(&cell))
+        .ok_or_else(|| Error::parse(format!("no '{}' in the maze", cell.to_string().trim())))
 }
 
 fn find_cell_distances(maze: &Grid<Cell>, from_location: Location) -> HashMap<Location, usize> {
@@ -85,8 +91,10 @@ fn find_cheat_routes(
     start_location: &Location,
     cell_distances_to_end: &HashMap<Location, usize>,
     max_cheat_length: usize,
-) -> HashMap<(Location, Location), usize> {
-    let start_distance = *cell_distances_to_end.get(start_location).unwrap();
+) -> Result<HashMap<(Location, Location), usize>> {
+    let start_distance = *cell_distances_to_end
+        .get(start_location)
+        .ok_or_else(|| Error::no_solution("the end can't be reached from the start"))?;
 
     // Remove all cell distances that are futher away from the end than the start, as we don't want to cheat from those routes.
     let cell_distances_to_end: HashMap<_, _> = cell_distances_to_end
@@ -95,7 +103,7 @@ fn find_cheat_routes(
         .map(|(k, v)| (k.clone(), *v))
         .collect();
 
-    cell_distances_to_end
+    Ok(cell_distances_to_end
         // Get the product of the cell distances with itself, limited only to
         .iter()
         .flat_map(|a| [a].into_iter().cycle().zip(cell_distances_to_end.iter()))
@@ -110,53 +118,37 @@ fn find_cheat_routes(
                 a_distance.abs_diff(*b_distance) - a_location.manhattan_distance(b_location),
             )
         })
-        .collect()
+        .collect())
 }
 
-fn part1(buf: &mut dyn Read) {
-    let maze = parse(buf);
-
-    let start_location = maze
-        .iter_locations()
-        .find(|v| maze.get(v) == Some(&Cell::Start))
-        .unwrap();
-    let end_location = maze
-        .iter_locations()
-        .find(|v| maze.get(v) == Some(&Cell::End))
-        .unwrap();
+fn part1(maze: &Grid<Cell>) -> Result<Answer> {
+    let start_location = find_cell(maze, Cell::Start)?;
+    let end_location = find_cell(maze, Cell::End)?;
 
-    let cell_distances_to_end = find_cell_distances(&maze, end_location);
+    let cell_distances_to_end = find_cell_distances(maze, end_location);
 
-    let cheat_routes = find_cheat_routes(&start_location, &cell_distances_to_end, 2);
+    let cheat_routes = find_cheat_routes(&start_location, &cell_distances_to_end, 2)?;
 
     let result: usize = cheat_routes.iter().filter(|(_, v)| **v >= 100).count();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let maze = parse(buf);
-
-    let start_location = maze
-        .iter_locations()
-        .find(|v| maze.get(v) == Some(&Cell::Start))
-        .unwrap();
-    let end_location = maze
-        .iter_locations()
-        .find(|v| maze.get(v) == Some(&Cell::End))
-        .unwrap();
+fn part2(maze: &Grid<Cell>) -> Result<Answer> {
+    let start_location = find_cell(maze, Cell::Start)?;
+    let end_location = find_cell(maze, Cell::End)?;
 
-    let cell_distances_to_end = find_cell_distances(&maze, end_location);
+    let cell_distances_to_end = find_cell_distances(maze, end_location);
 
     let cheat_routes: HashMap<(Location, Location), usize> =
-        find_cheat_routes(&start_location, &cell_distances_to_end, 20);
+        find_cheat_routes(&start_location, &cell_distances_to_end, 20)?;
 
     let valid_cheat_routes: HashSet<(Location, Location)> = cheat_routes
         .into_iter()
         .filter_map(|(k, v)| (v >= 100).then_some(k))
         .collect();
     let result = valid_cheat_routes.len();
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 20, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 20, parse, part1, part2,));
@@ -7,11 +7,27 @@ use std::{
 use grid::Location;
 use lazy_static::lazy_static;
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<String> {
+#[derive(Debug)]
+struct Code {
+    keys: String,
+    value: usize,
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Code>> {
     let reader = BufReader::new(buf);
-    reader.lines().map_while(Result::ok).collect()
+    let mut codes = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let value = line
+            .strip_suffix('A')
+            .filter(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| Error::parse(format!("invalid code '{}'", line)).at_line(i + 1))?;
+        codes.push(Code { keys: line, value });
+    }
+    Ok(codes)
 }
 
 lazy_static! {
@@ -119,26 +135,21 @@ fn number_route(from: char, to: char, x_first: bool, steps: usize) -> String {
     seq
 }
 
-fn part1(buf: &mut dyn Read) {
-    let codes = parse(buf);
-
+fn part1(codes: &[Code]) -> Result<Answer> {
     let result: usize = codes
         .iter()
         .map(|code| {
             let route: String = ['A']
                 .into_iter()
-                .chain(code.chars())
-                .zip(code.chars())
+                .chain(code.keys.chars())
+                .zip(code.keys.chars())
                 .map(|(from, to)| cheapest_routes.get(&(from, to)).unwrap().deref())
                 .collect();
-            // dbg!(code, &route);
-            let result = code.strip_suffix("A").unwrap().parse::<usize>().unwrap() * route.len();
-            // dbg!(result)
-            result
+            code.value * route.len()
         })
         .sum();
 
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 lazy_static! {
@@ -209,16 +220,14 @@ fn new_transitions(transitions: &Transitions) -> Transitions {
         })
 }
 
-fn part2(buf: &mut dyn Read) {
-    let codes = parse(buf);
-
+fn part2(codes: &[Code]) -> Result<Answer> {
     let level_3_routes: Vec<String> = codes
         .iter()
         .map(|code| {
             let route: String = ['A']
                 .into_iter()
-                .chain(code.chars())
-                .zip(code.chars())
+                .chain(code.keys.chars())
+                .zip(code.keys.chars())
                 .map(|(from, to)| cheapest_routes_2.get(&(from, to)).unwrap().deref())
                 .collect();
             route
@@ -240,15 +249,27 @@ fn part2(buf: &mut dyn Read) {
     let result: usize = codes
         .iter()
         .zip(transitions.iter())
-        .map(|(code, transitions)| {
-            let code_val = code.strip_suffix("A").unwrap().parse::<usize>().unwrap();
-            let route_length = transitions.values().sum::<usize>();
-            // dbg!(code, route_length);
-            code_val * route_length
-        })
+        .map(|(code, transitions)| code.value * transitions.values().sum::<usize>())
         .sum();
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 21, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 21, parse, part1, part2,));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let codes = parse(&mut "029A\n980A\n".as_bytes()).unwrap();
+        assert_eq!(codes[0].value, 29);
+        assert_eq!(codes[1].keys, "980A");
+
+        assert!(parse(&mut "029\n".as_bytes()).is_err());
+        assert!(parse(&mut "A\n".as_bytes()).is_err());
+        let error = parse(&mut "029A\n99999999999999999999999A\n".as_bytes()).unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 struct SecretGenerator {
     state: u32,
@@ -34,26 +34,26 @@ impl Iterator for SecretGenerator {
     }
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<u32> {
+fn parse(buf: &mut dyn Read) -> Result<Vec<u32>> {
     let reader = BufReader::new(buf);
     reader
         .lines()
-        .map_while(|line| line.ok()?.parse().ok())
+        .enumerate()
+        .map(|(i, line)| line?.parse().at_line(i + 1))
         .collect()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let seeds = parse(buf);
+fn part1(seeds: &[u32]) -> Result<Answer> {
     let result: usize = seeds
-        .into_iter()
+        .iter()
+        .copied()
         .map(|seed| SecretGenerator::new(seed).nth(1999).unwrap() as usize)
         .sum();
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let seeds = parse(buf);
-    let monkey_prices = seeds.into_iter().map(|seed| {
+fn part2(seeds: &[u32]) -> Result<Answer> {
+    let monkey_prices = seeds.iter().copied().map(|seed| {
         SecretGenerator::new(seed)
             .take(2000)
             .map(|price| (price % 10) as i8)
@@ -85,12 +85,15 @@ fn part2(buf: &mut dyn Read) {
     }
 
     // Find the maximum score (the sequence doesn't matter)
-    let result = change_sequence_score.values().max().unwrap();
+    let result = change_sequence_score
+        .values()
+        .max()
+        .ok_or_else(|| Error::no_solution("there are no buyers"))?;
 
-    println!("Part 2: {}", result);
+    Ok((*result).into())
 }
 
-inventory::submit!(Aoc::new(2024, 22, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 22, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -3,26 +3,28 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<(String, String)> {
+fn parse(buf: &mut dyn Read) -> Result<Vec<(String, String)>> {
     let reader = BufReader::new(buf);
     reader
         .lines()
-        .map_while(|line| {
-            line.ok()?
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line?;
+            let (a, b) = line
                 .split_once('-')
-                .and_then(|(a, b)| Some((a.parse().ok()?, b.parse().ok()?)))
+                .ok_or_else(|| Error::parse("expected '<a>-<b>'").at_line(i + 1))?;
+            Ok((a.to_owned(), b.to_owned()))
         })
         .collect()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let pairs = parse(buf);
+fn part1(pairs: &[(String, String)]) -> Result<Answer> {
     let connections: HashMap<_, HashSet<String>> =
-        pairs.into_iter().fold(HashMap::new(), |mut acc, (a, b)| {
+        pairs.iter().fold(HashMap::new(), |mut acc, (a, b)| {
             acc.entry(a.clone()).or_default().insert(b.clone());
-            acc.entry(b).or_default().insert(a);
+            acc.entry(b.clone()).or_default().insert(a.clone());
             acc
         });
     let mut networks: HashSet<[&String; 3]> = HashSet::new();
@@ -42,13 +44,12 @@ fn part1(buf: &mut dyn Read) {
     }
 
     let result = networks.len();
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let pairs = parse(buf);
+fn part2(pairs: &[(String, String)]) -> Result<Answer> {
     let connections: HashMap<_, HashSet<String>> =
-        pairs.into_iter().fold(HashMap::new(), |mut acc, (a, b)| {
+        pairs.iter().fold(HashMap::new(), |mut acc, (a, b)| {
             acc.entry(a.clone()).or_default().insert(b.clone());
             acc.entry(b.clone()).or_default().insert(a.clone());
             acc
@@ -71,12 +72,17 @@ fn part2(buf: &mut dyn Read) {
 
     let mut complete_networks: Vec<_> = network_map.values().collect();
     complete_networks.sort_by_key(|network| network.len());
-    let mut largest_network: Vec<_> = complete_networks.last().unwrap().iter().cloned().collect();
+    let mut largest_network: Vec<_> = complete_networks
+        .last()
+        .ok_or_else(|| Error::no_solution("there are no connections"))?
+        .iter()
+        .cloned()
+        .collect();
     largest_network.sort();
 
     let result = largest_network.join(",");
 
-    println!("Part 2: {}", result);
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2024, 23, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 23, parse, part1, part2,));
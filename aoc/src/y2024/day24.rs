@@ -3,7 +3,7 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 enum GateType {
@@ -95,64 +95,84 @@ struct Data {
     gates: Vec<Gate>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
+fn parse_gate(line: &str) -> Result<Gate> {
+    let (gate, output) = line
+        .split_once("->")
+        .ok_or_else(|| Error::parse("expected '<a> <gate> <b> -> <output>'"))?;
+    let mut parts = gate.split_whitespace();
+    let (Some(input_a), Some(gate_type), Some(input_b), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::parse("expected '<a> <gate> <b> -> <output>'"));
+    };
+
+    let gate_type = match gate_type {
+        "AND" => GateType::And,
+        "OR" => GateType::Or,
+        "XOR" => GateType::Xor,
+        _ => return Err(Error::parse(format!("invalid gate type '{}'", gate_type))),
+    };
+
+    Ok(Gate {
+        input_a: input_a.to_string(),
+        input_b: input_b.to_string(),
+        gtype: gate_type,
+        output: output.trim().to_string(),
+    })
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Data> {
     let reader = BufReader::new(buf);
-    let lines: Result<Vec<_>, _> = reader.lines().collect();
-    let lines = lines.unwrap();
-    let split_point = lines
-        .iter()
-        .enumerate()
-        .find_map(|(i, v)| v.is_empty().then_some(i))
-        .unwrap();
-    let (state_lines, gate_lines) = lines.split_at(split_point);
+    let mut lines = reader.lines().enumerate();
 
-    let state = state_lines
-        .iter()
-        .map_while(|line| {
-            if line.is_empty() {
-                return None;
-            }
+    let mut state = HashMap::new();
+    for (i, line) in lines.by_ref() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
 
-            let (input, state) = line.split_once(':')?;
-            match state.trim() {
-                "0" => Some((input.to_string(), false)),
-                "1" => Some((input.to_string(), true)),
-                _ => panic!("unrecognised state"),
+        let (input, value) = line
+            .split_once(':')
+            .ok_or_else(|| Error::parse("expected '<wire>: <value>'").at_line(i + 1))?;
+        let value = match value.trim() {
+            "0" => false,
+            "1" => true,
+            _ => {
+                return Err(
+                    Error::parse(format!("invalid wire value '{}'", value.trim())).at_line(i + 1),
+                )
             }
-        })
-        .collect();
+        };
+        state.insert(input.to_string(), value);
+    }
 
-    let gates = gate_lines
-        .iter()
-        .skip(1)
-        .map_while(|line| {
-            let (gate, output) = line.split_once("->")?;
-
-            let (input_a, remaining) = gate.trim().split_once(' ')?;
-            let (gate_type, input_b) = remaining.trim().split_once(' ')?;
-
-            let gate_type = match gate_type.trim() {
-                "AND" => GateType::And,
-                "OR" => GateType::Or,
-                "XOR" => GateType::Xor,
-                _ => panic!("Invalid gate type"),
-            };
-
-            Some(Gate {
-                input_a: input_a.trim().to_string(),
-                input_b: input_b.trim().to_string(),
-                gtype: gate_type,
-                output: output.trim().to_string(),
-            })
-        })
-        .collect();
+    let mut gates = Vec::new();
+    let mut gate_lines = Vec::new();
+    for (i, line) in lines {
+        gates.push(parse_gate(&line?).at_line(i + 1)?);
+        gate_lines.push(i + 1);
+    }
+
+    let outputs: HashSet<_> = gates.iter().map(|gate| &gate.output).collect();
+    for (gate, line) in gates.iter().zip(gate_lines) {
+        for input in [&gate.input_a, &gate.input_b] {
+            if !state.contains_key(input) && !outputs.contains(input) {
+                return Err(Error::parse(format!("unknown wire '{}'", input)).at_line(line));
+            }
+        }
+    }
 
-    Data { state, gates }
+    Ok(Data { state, gates })
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Data { gates, mut state } = parse(buf);
-    let gates: HashMap<_, _> = gates.into_iter().map(|g| (g.output.clone(), g)).collect();
+fn part1(data: &Data) -> Result<Answer> {
+    let Data { gates, state } = data;
+    let mut state = state.clone();
+    let gates: HashMap<_, _> = gates
+        .iter()
+        .map(|g| (g.output.clone(), g.clone()))
+        .collect();
 
     let mut z_keys: Vec<_> = gates
         .keys()
@@ -165,14 +185,14 @@ fn part1(buf: &mut dyn Read) {
         .into_iter()
         .map(|g| {
             g.get_output_and_update_state(&mut state, &gates, 1000)
-                .unwrap()
+                .ok_or_else(|| Error::no_solution("the circuit contains a loop"))
         })
-        .collect();
+        .collect::<Result<_>>()?;
     let mut result: usize = 0;
     for (i, _) in z_values.into_iter().enumerate().filter(|(_, v)| *v) {
         result |= 1 << i;
     }
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
 fn get_initial_state(x: usize, y: usize, bits: u8) -> HashMap<String, bool> {
@@ -239,9 +259,12 @@ fn check_gates(gates: &HashMap<String, Gate>) -> Option<u8> {
 
 const BITS: u8 = 45;
 
-fn part2(buf: &mut dyn Read) {
-    let Data { gates, .. } = parse(buf);
-    let mut gates: HashMap<_, _> = gates.into_iter().map(|g| (g.output.clone(), g)).collect();
+fn part2(data: &Data) -> Result<Answer> {
+    let Data { gates, .. } = data;
+    let mut gates: HashMap<_, _> = gates
+        .iter()
+        .map(|g| (g.output.clone(), g.clone()))
+        .collect();
 
     let mut swaps = vec![];
 
@@ -269,7 +292,6 @@ fn part2(buf: &mut dyn Read) {
             .chain(reverse_depends(&y_input, &gates, 4))
             .filter(|g| !g.starts_with('x') && !g.starts_with('y'))
             .collect();
-        eprintln!("Something has been swapped from {:?}", possibly_swapped);
 
         let possibly_swapped: Vec<_> = possibly_swapped.into_iter().collect();
         // Perform the swapping game
@@ -282,14 +304,12 @@ fn part2(buf: &mut dyn Read) {
                 if result.is_none() {
                     swaps.push(a.clone());
                     swaps.push(b.clone());
-                    eprintln!("Swap solution is {} <> {}", a, b);
                     gates = swapped_gates;
                     break 'swapper;
                 } else if let Some(next_incorrect_bit) = result {
                     if next_incorrect_bit > incorrect_bit {
                         swaps.push(a.clone());
                         swaps.push(b.clone());
-                        eprintln!("Swap solution is probably {} <> {}", a, b);
                         gates = swapped_gates;
                         break 'swapper;
                     }
@@ -299,10 +319,10 @@ fn part2(buf: &mut dyn Read) {
     }
 
     swaps.sort();
-    println!("Part 2: {}", swaps.join(","));
+    Ok(swaps.join(",").into())
 }
 
-inventory::submit!(Aoc::new(2024, 24, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 24, parse, part1, part2,));
 
 // Harolding
 const RANDOM_NUMBERS: &[usize] = &[
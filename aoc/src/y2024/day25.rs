@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
 type Pins = [u8; 5];
 
@@ -9,27 +9,39 @@ struct Data {
     keys: Vec<Pins>,
 }
 
-fn parse(buf: &mut dyn Read) -> Data {
+fn parse(buf: &mut dyn Read) -> Result<Data> {
     let mut buffer = Vec::new();
-    buf.read_to_end(&mut buffer).unwrap();
-    let buffer = String::from_utf8(buffer).unwrap();
+    buf.read_to_end(&mut buffer)?;
+    let buffer = String::from_utf8(buffer)?;
 
     let mut locks = vec![];
     let mut keys = vec![];
+    let mut line_number = 1;
     for pinset in buffer.split("\n\n") {
         let is_lock = pinset.starts_with("#####");
         let mut pins: Pins = [0, 0, 0, 0, 0];
+        let first_line = line_number;
 
         for line in pinset.lines() {
-            line.chars().enumerate().for_each(|(i, p)| {
-                if p == '#' {
-                    pins[i] += 1;
+            for (i, p) in line.chars().enumerate() {
+                match (p, pins.get_mut(i)) {
+                    ('#', Some(pin)) => *pin += 1,
+                    ('.', Some(_)) => {}
+                    _ => {
+                        return Err(
+                            Error::parse(format!("unexpected '{}'", p)).at(line_number, i + 1)
+                        );
+                    }
                 }
-            });
+            }
+            line_number += 1;
         }
+        line_number += 1;
 
         for pin in pins.iter_mut() {
-            *pin -= 1;
+            *pin = pin
+                .checked_sub(1)
+                .ok_or_else(|| Error::parse("a column has no pin").at_line(first_line))?;
         }
 
         if is_lock {
@@ -38,16 +50,15 @@ fn parse(buf: &mut dyn Read) -> Data {
             keys.push(pins);
         }
     }
-    Data { locks, keys }
+    Ok(Data { locks, keys })
 }
 
 fn key_fits_lock(key: &Pins, lock: &Pins) -> bool {
     key.iter().zip(lock).all(|(kp, lp)| kp + lp <= 5)
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Data { locks, keys } = parse(buf);
-    dbg!(&locks, &keys);
+fn part1(data: &Data) -> Result<Answer> {
+    let Data { locks, keys } = data;
 
     let mut results = 0;
     for lock in locks.iter() {
@@ -57,9 +68,11 @@ fn part1(buf: &mut dyn Read) {
             }
         }
     }
-    println!("Part 1: {}", results);
+    Ok(results.into())
 }
 
-fn part2(_: &mut dyn Read) {}
+fn part2(_: &Data) -> Result<Answer> {
+    Ok(Answer::Empty)
+}
 
-inventory::submit!(Aoc::new(2024, 25, part1, part2,));
+inventory::submit!(parsed_aoc!(2024, 25, parse, part1, part2,));
@@ -1,23 +1,26 @@
 use std::io::{BufRead, BufReader, Read};
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 type Step = i16;
 type Position = i16;
 
-fn parse(buf: &mut dyn Read) -> Vec<Step> {
+fn parse_step(line: &str) -> Result<Step> {
+    if let Some(clicks) = line.strip_prefix('L') {
+        Ok(-clicks.parse::<Step>()?)
+    } else if let Some(clicks) = line.strip_prefix('R') {
+        Ok(clicks.parse()?)
+    } else {
+        Err(Error::parse("expected a rotation starting with 'L' or 'R'"))
+    }
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Step>> {
     let buf_read = BufReader::new(buf);
     buf_read
         .lines()
-        .map_while(|line| {
-            let line = line.ok()?;
-            let clicks: Step = line[1..].parse().unwrap();
-            if line.starts_with("L") {
-                Some(-clicks)
-            } else {
-                Some(clicks)
-            }
-        })
+        .enumerate()
+        .map(|(i, line)| parse_step(&line?).at_line(i + 1))
         .collect()
 }
 
@@ -59,21 +62,19 @@ fn positions_with_zero_passes(
     }))
 }
 
-fn part1(buf: &mut dyn Read) {
-    let steps = parse(buf);
-    let zero_count = positions(steps.into_iter()).filter(|p| p == &0).count();
-    println!("Part 1: {}", zero_count);
+fn part1(steps: &[Step]) -> Result<Answer> {
+    let zero_count = positions(steps.iter().copied()).filter(|p| p == &0).count();
+    Ok(zero_count.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let steps = parse(buf);
-    let zero_pass_count: i16 = positions_with_zero_passes(steps.into_iter())
+fn part2(steps: &[Step]) -> Result<Answer> {
+    let zero_pass_count: i16 = positions_with_zero_passes(steps.iter().copied())
         .map(|(_, v)| v)
         .sum();
-    println!("Part 2: {}", zero_pass_count);
+    Ok(zero_pass_count.into())
 }
 
-inventory::submit!(Aoc::new(2025, 1, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 1, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -3,19 +3,27 @@ use std::{
     ops::RangeInclusive,
 };
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<RangeInclusive<usize>> {
+fn parse_range(range_str: &str) -> Result<RangeInclusive<usize>> {
+    let (a, b) = range_str
+        .split_once("-")
+        .ok_or_else(|| Error::parse(format!("expected a range, found '{}'", range_str.trim())))?;
+    let a: usize = a.trim().parse()?;
+    let b: usize = b.trim().parse()?;
+    if a == 0 {
+        return Err(Error::parse("ids start at 1"));
+    }
+    Ok(a..=b)
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<RangeInclusive<usize>>> {
     let mut buf_read = BufReader::new(buf);
     let mut line = String::new();
-    buf_read.read_line(&mut line).unwrap();
+    buf_read.read_line(&mut line)?;
     line.split(",")
-        .map(|range_str| {
-            let (a, b) = range_str.split_once("-").unwrap();
-            let a: usize = a.trim().parse().unwrap();
-            let b: usize = b.trim().parse().unwrap();
-            a..=b
-        })
+        .map(parse_range)
+        .map(|range| range.at_line(1))
         .collect()
 }
 
@@ -38,27 +46,27 @@ fn is_invalid_id(id: usize) -> bool {
     (2..=digits).any(|repeats| id_has_repeats(id, repeats))
 }
 
-fn part1(buf: &mut dyn Read) {
-    let id_ranges = parse(buf);
+fn part1(id_ranges: &[RangeInclusive<usize>]) -> Result<Answer> {
     let answer: usize = id_ranges
-        .into_iter()
+        .iter()
+        .cloned()
         .flatten()
         .filter(|id| id_has_repeats(*id, 2))
         .sum();
-    println!("Part 1: {}", answer);
+    Ok(answer.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let id_ranges = parse(buf);
+fn part2(id_ranges: &[RangeInclusive<usize>]) -> Result<Answer> {
     let answer: usize = id_ranges
-        .into_iter()
+        .iter()
+        .cloned()
         .flatten()
         .filter(|id| is_invalid_id(*id))
         .sum();
-    println!("Part 2: {}", answer);
+    Ok(answer.into())
 }
 
-inventory::submit!(Aoc::new(2025, 2, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 2, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -66,35 +74,35 @@ mod tests {
 
     #[test]
     fn test_id_has_repeats() {
-        assert_eq!(id_has_repeats(11, 2), true);
-        assert_eq!(id_has_repeats(22, 2), true);
-        assert_eq!(id_has_repeats(99, 2), true);
-        assert_eq!(id_has_repeats(1010, 2), true);
-        assert_eq!(id_has_repeats(1188511885, 2), true);
-        assert_eq!(id_has_repeats(222222, 2), true);
-        assert_eq!(id_has_repeats(446446, 2), true);
-        assert_eq!(id_has_repeats(38593859, 2), true);
+        assert!(id_has_repeats(11, 2));
+        assert!(id_has_repeats(22, 2));
+        assert!(id_has_repeats(99, 2));
+        assert!(id_has_repeats(1010, 2));
+        assert!(id_has_repeats(1188511885, 2));
+        assert!(id_has_repeats(222222, 2));
+        assert!(id_has_repeats(446446, 2));
+        assert!(id_has_repeats(38593859, 2));
 
-        assert_eq!(id_has_repeats(123123, 2), true);
-        assert_eq!(id_has_repeats(35535, 2), false);
-        assert_eq!(id_has_repeats(123124, 2), false);
-        assert_eq!(id_has_repeats(101, 2), false);
+        assert!(id_has_repeats(123123, 2));
+        assert!(!id_has_repeats(35535, 2));
+        assert!(!id_has_repeats(123124, 2));
+        assert!(!id_has_repeats(101, 2));
     }
 
     #[test]
     fn test_is_invalid_id() {
-        assert_eq!(is_invalid_id(11), true);
-        assert_eq!(is_invalid_id(22), true);
-        assert_eq!(is_invalid_id(99), true);
-        assert_eq!(is_invalid_id(111), true);
-        assert_eq!(is_invalid_id(999), true);
-        assert_eq!(is_invalid_id(1010), true);
-        assert_eq!(is_invalid_id(1188511885), true);
-        assert_eq!(is_invalid_id(222222), true);
-        assert_eq!(is_invalid_id(446446), true);
-        assert_eq!(is_invalid_id(565656), true);
-        assert_eq!(is_invalid_id(824824824), true);
-        assert_eq!(is_invalid_id(2121212121), true);
-        assert_eq!(is_invalid_id(38593859), true);
+        assert!(is_invalid_id(11));
+        assert!(is_invalid_id(22));
+        assert!(is_invalid_id(99));
+        assert!(is_invalid_id(111));
+        assert!(is_invalid_id(999));
+        assert!(is_invalid_id(1010));
+        assert!(is_invalid_id(1188511885));
+        assert!(is_invalid_id(222222));
+        assert!(is_invalid_id(446446));
+        assert!(is_invalid_id(565656));
+        assert!(is_invalid_id(824824824));
+        assert!(is_invalid_id(2121212121));
+        assert!(is_invalid_id(38593859));
     }
 }
@@ -3,12 +3,19 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<String> {
+fn parse(buf: &mut dyn Read) -> Result<Vec<String>> {
     let buf_reader = BufReader::new(buf);
-    let lines: Result<_, _> = buf_reader.lines().collect();
-    lines.unwrap()
+    let mut banks = Vec::new();
+    for (i, line) in buf_reader.lines().enumerate() {
+        let line = line?;
+        if let Some((j, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(Error::parse(format!("invalid joltage '{}'", c)).at(i + 1, j + 1));
+        }
+        banks.push(line);
+    }
+    Ok(banks)
 }
 
 fn find_highest_joltage_2(bank: &str) -> u8 {
@@ -45,13 +52,12 @@ fn find_highest_joltage_2(bank: &str) -> u8 {
     0
 }
 
-fn part1(buf: &mut dyn Read) {
-    let banks = parse(buf);
+fn part1(banks: &[String]) -> Result<Answer> {
     let result: u32 = banks
         .iter()
         .map(|bank| find_highest_joltage_2(bank) as u32)
         .sum();
-    println!("Part 1: {result}");
+    Ok(result.into())
 }
 
 fn find_highest_joltage_n(bank: &str, n: usize) -> Option<u64> {
@@ -82,16 +88,20 @@ fn find_highest_joltage_n(bank: &str, n: usize) -> Option<u64> {
     None
 }
 
-fn part2(buf: &mut dyn Read) {
-    let banks = parse(buf);
+fn part2(banks: &[String]) -> Result<Answer> {
     let result: u64 = banks
         .iter()
-        .map(|bank| find_highest_joltage_n(bank, 12).unwrap())
-        .sum();
-    println!("Part 2: {result}");
+        .enumerate()
+        .map(|(i, bank)| {
+            find_highest_joltage_n(bank, 12).ok_or_else(|| {
+                Error::no_solution("the bank has fewer than 12 batteries").at_line(i + 1)
+            })
+        })
+        .sum::<Result<_>>()?;
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2025, 3, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 3, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -2,30 +2,38 @@ use std::io::{BufRead, BufReader, Read};
 
 use grid::{Grid, Location, Offset};
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum Content {
     Roll,
     Nothing,
 }
 
-fn parse(buf: &mut dyn Read) -> Grid<Content> {
+fn parse(buf: &mut dyn Read) -> Result<Grid<Content>> {
     let reader = BufReader::new(buf);
     let mut lines = Vec::new();
     let mut line_length = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i > 0 && line.len() != line_length {
+            return Err(Error::parse(format!(
+                "expected a row of {} cells, found {}",
+                line_length,
+                line.len()
+            ))
+            .at_line(i + 1));
+        }
         line_length = line.len();
         lines.push(line);
     }
-    Grid::new(
+    Ok(Grid::new(
         lines.join("").chars().map(|char| match char {
             '@' => Content::Roll,
             _ => Content::Nothing,
         }),
         line_length,
-    )
+    ))
 }
 
 fn adjacent(location: &Location) -> impl Iterator<Item = Location> + use<'_> {
@@ -41,9 +49,7 @@ fn adjacent(location: &Location) -> impl Iterator<Item = Location> + use<'_> {
     ]
     .into_iter()
     .filter_map(move |(x, y)| {
-        if -x > location.x as isize {
-            None
-        } else if -y > location.y as isize {
+        if -x > location.x as isize || -y > location.y as isize {
             None
         } else {
             Some(location + &Offset { x, y })
@@ -66,13 +72,12 @@ fn is_movable_roll(grid: &Grid<Content>, location: &Location) -> bool {
     adjacent_roll_count < 4
 }
 
-fn part1(buf: &mut dyn Read) {
-    let grid = parse(buf);
+fn part1(grid: &Grid<Content>) -> Result<Answer> {
     let movable_roll_count = grid
         .iter_locations()
-        .filter(|location| is_movable_roll(&grid, location))
+        .filter(|location| is_movable_roll(grid, location))
         .count();
-    println!("Part 1: {movable_roll_count}");
+    Ok(movable_roll_count.into())
 }
 
 fn remove_rolls(grid: &Grid<Content>) -> (Grid<Content>, usize) {
@@ -94,8 +99,8 @@ fn remove_rolls(grid: &Grid<Content>) -> (Grid<Content>, usize) {
     (new_grid, removed_count)
 }
 
-fn part2(buf: &mut dyn Read) {
-    let mut grid = parse(buf);
+fn part2(grid: &Grid<Content>) -> Result<Answer> {
+    let mut grid = grid.clone();
     let mut removed_rolls = 0;
     loop {
         let (new_grid, removed_count) = remove_rolls(&grid);
@@ -105,10 +110,10 @@ fn part2(buf: &mut dyn Read) {
         removed_rolls += removed_count;
         grid = new_grid
     }
-    println!("Part 2: {removed_rolls}")
+    Ok(removed_rolls.into())
 }
 
-inventory::submit!(Aoc::new(2025, 4, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 4, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
@@ -117,7 +122,7 @@ mod tests {
     #[test]
     fn example() {
         let data = b"..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
-        let grid = parse(&mut &data[..]);
+        let grid = parse(&mut &data[..]).unwrap();
         let movable_roll_count = grid
             .iter_locations()
             .filter(|location| is_movable_roll(&grid, location))
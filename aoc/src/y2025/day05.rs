@@ -1,43 +1,50 @@
 use std::io::{BufRead, BufReader, Read};
 use std::ops::RangeInclusive;
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 type Ingredient = u64;
 
-fn parse(buf: &mut dyn Read) -> (Vec<RangeInclusive<Ingredient>>, Vec<Ingredient>) {
+fn parse_range(line: &str) -> Result<RangeInclusive<Ingredient>> {
+    let (a, b) = line
+        .split_once('-')
+        .ok_or_else(|| Error::parse("expected a range '<a>-<b>'"))?;
+    let a: Ingredient = a.parse()?;
+    let b: Ingredient = b.parse()?;
+    Ok(a..=b)
+}
+
+fn parse(buf: &mut dyn Read) -> Result<(Vec<RangeInclusive<Ingredient>>, Vec<Ingredient>)> {
     let buf_reader = BufReader::new(buf);
-    let mut lines = buf_reader.lines().into_iter();
+    let mut lines = buf_reader.lines().enumerate();
 
     let mut ranges = vec![];
-    loop {
-        let range_line = lines.next().unwrap().unwrap();
+    for (i, range_line) in lines.by_ref() {
+        let range_line = range_line?;
         if range_line.is_empty() {
             break;
         }
-        let (a, b) = range_line.split_once('-').unwrap();
-        let a: Ingredient = a.parse().unwrap();
-        let b: Ingredient = b.parse().unwrap();
-
-        ranges.push(a..=b);
+        ranges.push(parse_range(&range_line).at_line(i + 1)?);
     }
 
-    let values: Vec<Ingredient> = lines.map(|v| v.unwrap().parse().unwrap()).collect();
-    (ranges, values)
+    let values = lines
+        .map(|(i, line)| line?.parse().at_line(i + 1))
+        .collect::<Result<Vec<Ingredient>>>()?;
+    Ok((ranges, values))
 }
 
-fn part1(buf: &mut dyn Read) {
-    let (fresh_ranges, ingredients) = parse(buf);
+fn part1(data: &(Vec<RangeInclusive<Ingredient>>, Vec<Ingredient>)) -> Result<Answer> {
+    let (fresh_ranges, ingredients) = data;
 
     let fresh_ingredients_count = ingredients
         .iter()
         .filter(|ingredient| fresh_ranges.iter().any(|r| r.contains(ingredient)))
         .count();
-    println!("Part 1: {}", fresh_ingredients_count);
+    Ok(fresh_ingredients_count.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let (fresh_ranges, ingredients) = parse(buf);
+fn part2(data: &(Vec<RangeInclusive<Ingredient>>, Vec<Ingredient>)) -> Result<Answer> {
+    let (fresh_ranges, _ingredients) = data;
 
     let mut markers: Vec<(Ingredient, bool)> = vec![];
 
@@ -64,7 +71,7 @@ fn part2(buf: &mut dyn Read) {
             }
         }
     }
-    println!("Part 2: {}", possible_fresh_ingredient_count);
+    Ok(possible_fresh_ingredient_count.into())
 }
 
-inventory::submit!(Aoc::new(2025, 5, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 5, parse, part1, part2,));
@@ -1,6 +1,6 @@
 use std::io::{BufRead, BufReader, Read};
 
-use crate::Aoc;
+use crate::{Answer, Aoc, Error, Result};
 
 type Number = num_bigint::BigUint;
 
@@ -10,56 +10,59 @@ enum Operation {
     Multiply,
 }
 
-impl Operation {
-    fn perform(&self, a: Number, b: Number) -> Number {
-        match self {
-            Self::Add => a + b,
-            Self::Multiply => a * b,
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Input {
     rows: Vec<Vec<Number>>,
     operations: Vec<Operation>,
 }
 
-fn parse(buf: &mut dyn Read) -> Input {
+fn parse_operation(c: char) -> Result<Operation> {
+    match c {
+        '+' => Ok(Operation::Add),
+        '*' => Ok(Operation::Multiply),
+        _ => Err(Error::parse(format!("invalid operation '{}'", c))),
+    }
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Input> {
     let buf_reader = BufReader::new(buf);
 
     let mut operations: Option<Vec<Operation>> = None;
     let mut rows = vec![];
-    for line in buf_reader.lines() {
-        let line = line.unwrap();
+    for (i, line) in buf_reader.lines().enumerate() {
+        let line = line?;
         let cells = line.split(" ").filter(|c| !c.is_empty());
-        let numbers: Result<Vec<Number>, _> = cells.clone().map(|v| v.trim().parse()).collect();
+        let numbers: std::result::Result<Vec<Number>, _> =
+            cells.clone().map(|v| v.trim().parse()).collect();
         if let Ok(numbers) = numbers {
             rows.push(numbers);
         } else {
-            let operations_row: Result<Vec<Operation>, ()> = cells
-                .map(|v| match v {
-                    "+" => Ok(Operation::Add),
-                    "*" => Ok(Operation::Multiply),
-                    v => {
-                        eprintln!("'{}'", v);
-                        eprintln!("'{}'", line);
-                        Err(())
-                    }
+            let operations_row = cells
+                .map(|v| match v.chars().collect::<Vec<_>>()[..] {
+                    [c] => parse_operation(c),
+                    _ => Err(Error::parse(format!("invalid operation '{}'", v))),
                 })
-                .collect();
-            operations = Some(operations_row.unwrap());
+                .collect::<Result<Vec<_>>>()
+                .map_err(|e| e.at_line(i + 1))?;
+            operations = Some(operations_row);
         }
     }
 
-    Input {
-        operations: operations.unwrap(),
-        rows,
+    let operations = operations.ok_or_else(|| Error::parse("missing the row of operations"))?;
+    if let Some(i) = rows.iter().position(|row| row.len() != operations.len()) {
+        return Err(Error::parse(format!(
+            "expected {} numbers, found {}",
+            operations.len(),
+            rows[i].len()
+        ))
+        .at_line(i + 1));
     }
+
+    Ok(Input { operations, rows })
 }
 
-fn part1(buf: &mut dyn Read) {
-    let Input { rows, operations } = parse(buf);
+fn part1(buf: &mut dyn Read) -> Result<Answer> {
+    let Input { rows, operations } = parse(buf)?;
     let mut solutions: Vec<Number> = vec![];
     for (i, operation) in operations.iter().enumerate() {
         let inputs = rows.iter().map(|row| &row[i]);
@@ -70,21 +73,29 @@ fn part1(buf: &mut dyn Read) {
         solutions.push(result);
     }
     let answer: Number = solutions.iter().sum();
-    println!("Part 1: {}", answer);
+    Ok(answer.into())
 }
 
-fn part2(buf: &mut dyn Read) {
+fn part2(buf: &mut dyn Read) -> Result<Answer> {
     let buf_reader = BufReader::new(buf);
     let mut lines = buf_reader.lines();
     let mut columns: Vec<String> = lines
         .next()
-        .unwrap()
-        .unwrap()
+        .ok_or_else(|| Error::parse("the worksheet is empty"))??
         .chars()
         .map(|c| c.to_string())
         .collect();
-    for line in lines {
-        for (i, c) in line.unwrap().chars().enumerate() {
+    for (i, line) in lines.enumerate() {
+        let line = line?;
+        if line.len() > columns.len() {
+            return Err(Error::parse(format!(
+                "expected at most {} columns, found {}",
+                columns.len(),
+                line.len()
+            ))
+            .at_line(i + 2));
+        }
+        for (i, c) in line.chars().enumerate() {
             columns[i].push(c);
         }
     }
@@ -92,11 +103,16 @@ fn part2(buf: &mut dyn Read) {
     let mut operation: Option<Operation> = None;
     let mut values: Vec<Number> = vec![];
     let mut sum: Number = Default::default();
-    for mut column in columns.iter().map(|column| column.trim()) {
+    for (i, mut column) in columns.iter().map(|column| column.trim()).enumerate() {
         if column.is_empty() {
             {
                 // Do calculation
-                let operation = operation.unwrap();
+                let operation = operation.ok_or_else(|| {
+                    Error::parse(format!(
+                        "the problem ending at column {} has no operation",
+                        i
+                    ))
+                })?;
                 let value: Number = match operation {
                     Operation::Add => values.iter().sum(),
                     Operation::Multiply => values.iter().product(),
@@ -114,25 +130,21 @@ fn part2(buf: &mut dyn Read) {
         let last_char = column.chars().last().unwrap();
 
         if !last_char.is_numeric() {
-            operation = match last_char {
-                '+' => Some(Operation::Add),
-                '*' => Some(Operation::Multiply),
-                _ => panic!(),
-            };
+            operation = Some(parse_operation(last_char)?);
             column = column[0..column.len() - 1].trim();
         }
 
-        values.push(column.parse().unwrap())
+        values.push(column.parse()?)
     }
 
-    let operation = operation.unwrap();
+    let operation = operation.ok_or_else(|| Error::parse("the last problem has no operation"))?;
     let value: Number = match operation {
         Operation::Add => values.iter().sum(),
         Operation::Multiply => values.iter().product(),
     };
     sum += value;
 
-    println!("Part 2: {}", sum);
+    Ok(sum.into())
 }
 
 inventory::submit!(Aoc::new(2025, 6, part1, part2,));
@@ -1,4 +1,4 @@
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 use grid::Location;
 use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader, Read};
@@ -9,12 +9,12 @@ struct Input {
     splitters: HashSet<Location>,
 }
 
-fn parse(buf: &mut dyn Read) -> Input {
+fn parse(buf: &mut dyn Read) -> Result<Input> {
     let buf_reader = BufReader::new(buf);
     let mut beam_start: Option<Location> = None;
     let mut splitters = HashSet::new();
     for (y, line) in buf_reader.lines().enumerate() {
-        for (x, c) in line.unwrap().chars().enumerate() {
+        for (x, c) in line?.chars().enumerate() {
             if c == 'S' {
                 beam_start = Some(Location { x, y });
             } else if c == '^' {
@@ -23,10 +23,10 @@ fn parse(buf: &mut dyn Read) -> Input {
         }
     }
 
-    Input {
-        beam_start: beam_start.unwrap(),
+    Ok(Input {
+        beam_start: beam_start.ok_or_else(|| Error::parse("no beam start 'S' in the manifold"))?,
         splitters,
-    }
+    })
 }
 
 #[derive(Debug, Clone, Default)]
@@ -59,7 +59,7 @@ fn step_beam(beam: &Location, splitters: &HashSet<Location>) -> (Option<Location
     }
 }
 
-fn step_beams<'a>(state: &State, splitters: &HashSet<Location>) -> State {
+fn step_beams(state: &State, splitters: &HashSet<Location>) -> State {
     let mut new_state = State {
         beams: Default::default(),
         hit_splitters: state.hit_splitters.clone(),
@@ -67,7 +67,7 @@ fn step_beams<'a>(state: &State, splitters: &HashSet<Location>) -> State {
     };
 
     for (beam, strength) in &state.beams {
-        let (hit_splitter, new_beams) = step_beam(&beam, splitters);
+        let (hit_splitter, new_beams) = step_beam(beam, splitters);
         if let Some(hit_splitter) = hit_splitter {
             new_state.hit_splitters.insert(hit_splitter);
             for beam in new_beams {
@@ -80,40 +80,40 @@ fn step_beams<'a>(state: &State, splitters: &HashSet<Location>) -> State {
     new_state
 }
 
-fn part1(buf: &mut dyn Read) {
+fn part1(input: &Input) -> Result<Answer> {
     let Input {
         beam_start,
         splitters,
-    } = parse(buf);
+    } = input;
 
     let mut state = State {
-        beams: HashMap::from([(beam_start, 1)]),
+        beams: HashMap::from([(beam_start.clone(), 1)]),
         ..Default::default()
     };
 
     while !state.beams.is_empty() {
-        state = step_beams(&state, &splitters);
+        state = step_beams(&state, splitters);
     }
 
-    println!("Part 1: {}", state.hit_splitters.len());
+    Ok(state.hit_splitters.len().into())
 }
 
-fn part2(buf: &mut dyn Read) {
+fn part2(input: &Input) -> Result<Answer> {
     let Input {
         beam_start,
         splitters,
-    } = parse(buf);
+    } = input;
 
     let mut state = State {
-        beams: HashMap::from([(beam_start, 1)]),
+        beams: HashMap::from([(beam_start.clone(), 1)]),
         ..Default::default()
     };
 
     while !state.beams.is_empty() {
-        state = step_beams(&state, &splitters);
+        state = step_beams(&state, splitters);
     }
 
-    println!("Part 2: {}", state.timelines);
+    Ok(state.timelines.into())
 }
 
-inventory::submit!(Aoc::new(2025, 7, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 7, parse, part1, part2,));
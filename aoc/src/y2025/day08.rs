@@ -1,4 +1,4 @@
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read};
 
@@ -20,20 +20,30 @@ impl Vec3 {
     }
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<Vec3> {
+fn parse_vec3(line: &str) -> Result<Vec3> {
+    let values = line
+        .split(",")
+        .map(|v| v.parse::<u64>())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    match values[..] {
+        [x, y, z] => Ok(Vec3(x, y, z)),
+        _ => Err(Error::parse(format!(
+            "expected 3 coordinates, found {}",
+            values.len()
+        ))),
+    }
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Vec3>> {
     let buf_reader = BufReader::new(buf);
     buf_reader
         .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let values: Vec<u64> = line.split(",").map(|v| v.parse::<u64>().unwrap()).collect();
-            Vec3(values[0], values[1], values[2])
-        })
+        .enumerate()
+        .map(|(i, line)| parse_vec3(&line?).at_line(i + 1))
         .collect()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let junction_boxes = parse(buf);
+fn part1(junction_boxes: &[Vec3]) -> Result<Answer> {
     let mut parent_groups: Vec<_> = junction_boxes
         .iter()
         .map(|junction_box| vec![junction_box])
@@ -61,10 +71,8 @@ fn part1(buf: &mut dyn Read) {
     distances_by_length
         .sort_by(|(_, distance_a), (_, distance_b)| distance_a.total_cmp(distance_b));
 
-    dbg!(distances_by_length.len());
-
     // Connect shortest until we get to 1000
-    for ((box_a, box_b), distance) in distances_by_length.iter().take(1000) {
+    for ((box_a, box_b), _distance) in distances_by_length.iter().take(1000) {
         // If they're in the same group, do nothing
         let group_a = *group_link.get(box_a).unwrap();
         let group_b = *group_link.get(box_b).unwrap();
@@ -73,7 +81,7 @@ fn part1(buf: &mut dyn Read) {
         }
 
         // Link them
-        let linked_group = std::mem::replace(&mut parent_groups[group_b], vec![]);
+        let linked_group = std::mem::take(&mut parent_groups[group_b]);
         let target_group = &mut parent_groups[group_a];
         for linked_box in &linked_group {
             group_link.insert(linked_box, group_a);
@@ -83,11 +91,10 @@ fn part1(buf: &mut dyn Read) {
     let mut group_sizes: Vec<_> = parent_groups.iter().map(|group| group.len()).collect();
     group_sizes.sort();
     let result: usize = group_sizes.iter().rev().take(3).product();
-    println!("Part 1: {}", result);
+    Ok(result.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let junction_boxes = parse(buf);
+fn part2(junction_boxes: &[Vec3]) -> Result<Answer> {
     let mut parent_groups: Vec<_> = junction_boxes
         .iter()
         .map(|junction_box| vec![junction_box])
@@ -115,11 +122,9 @@ fn part2(buf: &mut dyn Read) {
     distances_by_length
         .sort_by(|(_, distance_a), (_, distance_b)| distance_a.total_cmp(distance_b));
 
-    dbg!(distances_by_length.len());
-
     let mut last_con = None;
     // Connect shortest until we get to 1000
-    for ((box_a, box_b), distance) in distances_by_length.iter() {
+    for ((box_a, box_b), _distance) in distances_by_length.iter() {
         // If they're in the same group, do nothing
         let group_a = *group_link.get(box_a).unwrap();
         let group_b = *group_link.get(box_b).unwrap();
@@ -129,7 +134,7 @@ fn part2(buf: &mut dyn Read) {
 
         last_con = Some((box_a, box_b));
         // Link them
-        let linked_group = std::mem::replace(&mut parent_groups[group_b], vec![]);
+        let linked_group = std::mem::take(&mut parent_groups[group_b]);
         let target_group = &mut parent_groups[group_a];
         for linked_box in &linked_group {
             group_link.insert(linked_box, group_a);
@@ -137,9 +142,10 @@ fn part2(buf: &mut dyn Read) {
         target_group.extend(linked_group);
     }
 
-    let last_con = last_con.unwrap();
+    let last_con =
+        last_con.ok_or_else(|| Error::no_solution("there are fewer than two junction boxes"))?;
 
-    println!("Part 2: {}", (last_con.0).0 * (last_con.1).0);
+    Ok(((last_con.0).0 * (last_con.1).0).into())
 }
 
-inventory::submit!(Aoc::new(2025, 8, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 8, parse, part1, part2,));
@@ -2,20 +2,24 @@ use std::io::{BufRead, BufReader, Read};
 
 use grid::Location;
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> Vec<Location> {
+fn parse_location(line: &str) -> Result<Location> {
+    let (x, y) = line
+        .split_once(",")
+        .ok_or_else(|| Error::parse("expected '<x>,<y>'"))?;
+    Ok(Location {
+        x: x.parse()?,
+        y: y.parse()?,
+    })
+}
+
+fn parse(buf: &mut dyn Read) -> Result<Vec<Location>> {
     let buf_reader = BufReader::new(buf);
     buf_reader
         .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let mut cells = line.split(",").map(|v| v.parse::<usize>().unwrap());
-            Location {
-                x: cells.next().unwrap(),
-                y: cells.next().unwrap(),
-            }
-        })
+        .enumerate()
+        .map(|(i, line)| parse_location(&line?).at_line(i + 1))
         .collect()
 }
 
@@ -29,8 +33,7 @@ fn area(a: &Location, b: &Location) -> usize {
     side_lengths.x * side_lengths.y
 }
 
-fn part1(buf: &mut dyn Read) {
-    let points = parse(buf);
+fn part1(points: &[Location]) -> Result<Answer> {
     let mut biggest_rect = 0;
     for (i, a) in points.iter().enumerate() {
         for b in points[(i + 1)..].iter() {
@@ -39,18 +42,20 @@ fn part1(buf: &mut dyn Read) {
         }
     }
 
-    println!("Part 1: {}", biggest_rect)
+    Ok(biggest_rect.into())
 }
 
 fn is_within_rect(point: &Location, rect_a: &Location, rect_b: &Location) -> bool {
-    return (rect_a.x < rect_b.x && rect_a.x < point.x && point.x < rect_b.x
+    (rect_a.x < rect_b.x && rect_a.x < point.x && point.x < rect_b.x
         || rect_a.x > rect_b.x && rect_a.x > point.x && point.x > rect_b.x)
         && (rect_a.y < rect_b.y && rect_a.y < point.y && point.y < rect_b.y
-            || rect_a.y > rect_b.y && rect_a.y > point.y && point.y > rect_b.y);
+            || rect_a.y > rect_b.y && rect_a.y > point.y && point.y > rect_b.y)
 }
 
-fn part2(buf: &mut dyn Read) {
-    let points = parse(buf);
+fn part2(points: &[Location]) -> Result<Answer> {
+    if points.is_empty() {
+        return Err(Error::no_solution("there are no red tiles"));
+    }
 
     // To determine if a rectangle leaks outside the bounds drawn by the points,
     // we calculate all points along the border of the shape, then check if any
@@ -91,7 +96,6 @@ fn part2(buf: &mut dyn Read) {
     // Find the biggest rectangle as before
     let mut biggest_rect = 0;
     for (i, a) in points.iter().enumerate() {
-        dbg!(i);
         for b in points[(i + 1)..].iter() {
             if border_points
                 .iter()
@@ -104,7 +108,7 @@ fn part2(buf: &mut dyn Read) {
         }
     }
 
-    println!("Part 2: {}", biggest_rect)
+    Ok(biggest_rect.into())
 }
 
-inventory::submit!(Aoc::new(2025, 9, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 9, parse, part1, part2,));
@@ -1,11 +1,11 @@
 use std::io::{BufRead, BufReader, Read};
 
 use good_lp::{
-    Expression, ProblemVariables, Solution, SolverModel, Variable, constraint, default_solver,
-    variable,
+    constraint, default_solver, variable, Expression, ProblemVariables, Solution, SolverModel,
+    Variable,
 };
 
-use crate::Aoc;
+use crate::{error::ResultExt, Answer, Error, Result};
 
 #[derive(Debug)]
 struct Machine {
@@ -14,54 +14,48 @@ struct Machine {
     joltage_requirement: Vec<u32>,
 }
 
-fn parse_line(line: &str) -> Machine {
-    let part_count = line.chars().filter(|c| c == &' ').count() + 1;
-    let mut parts = line.split(" ");
+fn parse_numbers(list: &str) -> Result<Vec<u32>> {
+    list.split(",")
+        .map(|v| v.parse::<u32>().map_err(Error::from))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<Machine> {
+    let parts: Vec<&str> = line.split(" ").collect();
+    let [lights, buttons @ .., joltage_requirement] = &parts[..] else {
+        return Err(Error::parse("expected lights and a joltage requirement"));
+    };
 
-    let lights: Vec<bool> = parts
-        .next()
-        .unwrap()
-        .trim_matches(&['[', ']'])
+    let lights: Vec<bool> = lights
+        .trim_matches(['[', ']'])
         .chars()
         .map(|c| match c {
-            '#' => true,
-            '.' => false,
-            _ => panic!("not a light: {}", c),
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(Error::parse(format!("not a light: '{}'", c))),
         })
-        .collect();
+        .collect::<Result<_>>()?;
 
-    let buttons: Vec<Vec<u32>> = parts
-        .clone()
-        .take(part_count - 2)
-        .map(|button| {
-            button
-                .trim_matches(&['(', ')'])
-                .split(",")
-                .map(|v| v.parse::<u32>().unwrap())
-                .collect()
-        })
-        .collect();
+    let buttons: Vec<Vec<u32>> = buttons
+        .iter()
+        .map(|button| parse_numbers(button.trim_matches(['(', ')'])))
+        .collect::<Result<_>>()?;
 
-    let joltage_requirement: Vec<u32> = parts
-        .last()
-        .unwrap()
-        .trim_matches(&['{', '}'])
-        .split(",")
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect();
+    let joltage_requirement = parse_numbers(joltage_requirement.trim_matches(['{', '}']))?;
 
-    Machine {
+    Ok(Machine {
         lights,
         buttons,
         joltage_requirement,
-    }
+    })
 }
 
-fn parse(buf: &mut dyn Read) -> Vec<Machine> {
+fn parse(buf: &mut dyn Read) -> Result<Vec<Machine>> {
     let buf_reader = BufReader::new(buf);
     buf_reader
         .lines()
-        .map(|line| parse_line(&line.unwrap()))
+        .enumerate()
+        .map(|(i, line)| parse_line(&line?).at_line(i + 1))
         .collect()
 }
 
@@ -78,45 +72,45 @@ fn button_to_mask(button: &[u32]) -> u32 {
 }
 
 fn binary_selector(i: u32, count: usize) -> impl Iterator<Item = usize> {
-    (0..count).filter_map(move |b| (i & (1 << b) != 0).then_some(b as usize))
+    (0..count).filter(move |b| i & (1 << b) != 0)
 }
 
-fn binary_select<'a, T>(i: u32, source: &'a [T]) -> Vec<&'a T> {
+fn binary_select<T>(i: u32, source: &[T]) -> Vec<&T> {
     binary_selector(i, source.len())
         .map(|j| &source[j])
         .collect()
 }
 
-fn button_combinations<'a>(buttons: &'a [Vec<u32>]) -> impl Iterator<Item = Vec<&'a Vec<u32>>> {
+fn button_combinations(buttons: &[Vec<u32>]) -> impl Iterator<Item = Vec<&Vec<u32>>> {
     (0..((2u32).pow(buttons.len() as u32))).map(|i| binary_select(i, buttons))
 }
 
-fn find_least_buttons_for_machine(machine: &Machine) -> usize {
+fn find_least_buttons_for_machine(machine: &Machine) -> Result<usize> {
     let target_mask = lights_to_mask(&machine.lights);
     button_combinations(&machine.buttons)
         .filter_map(|button_combination| {
             let button_count = button_combination.len();
             let result = button_combination
                 .iter()
-                .map(|button| button_to_mask(&button))
+                .map(|button| button_to_mask(button))
                 .fold(0, |acc, mask| acc ^ mask);
 
             (result == target_mask).then_some(button_count)
         })
         .min()
-        .unwrap()
+        .ok_or_else(|| Error::no_solution("no combination of buttons turns on the lights"))
 }
 
-fn part1(buf: &mut dyn Read) {
-    let machines = parse(buf);
+fn part1(machines: &[Machine]) -> Result<Answer> {
     let result: usize = machines
         .iter()
-        .map(|machine| find_least_buttons_for_machine(machine))
-        .sum();
-    println!("Part 1: {}", result);
+        .enumerate()
+        .map(|(i, machine)| find_least_buttons_for_machine(machine).at_line(i + 1))
+        .sum::<Result<_>>()?;
+    Ok(result.into())
 }
 
-fn find_least_buttons_for_machine_joltage(machine: &Machine) -> u32 {
+fn find_least_buttons_for_machine_joltage(machine: &Machine) -> Result<u32> {
     // Let's just solve it with Linear Programming
     let mut problem_variables = ProblemVariables::new();
     let variables: Vec<Variable> = machine
@@ -149,27 +143,36 @@ fn find_least_buttons_for_machine_joltage(machine: &Machine) -> u32 {
                 }),
         );
 
-    let solution = problem.solve().unwrap();
+    let solution = problem
+        .solve()
+        .map_err(|error| Error::no_solution(format!("the joltages can't be reached: {}", error)))?;
 
     let presses: f64 = variables.iter().map(|var| solution.value(*var)).sum();
-    dbg!(presses, presses as u32);
-    presses as u32
+    Ok(presses as u32)
 }
 
-fn part2(buf: &mut dyn Read) {
-    let machines = parse(buf);
+fn part2(machines: &[Machine]) -> Result<Answer> {
     let result: u32 = machines
         .iter()
-        .map(|machine| find_least_buttons_for_machine_joltage(machine))
-        .sum();
-    println!("Part 2: {}", result);
+        .enumerate()
+        .map(|(i, machine)| find_least_buttons_for_machine_joltage(machine).at_line(i + 1))
+        .sum::<Result<_>>()?;
+    Ok(result.into())
 }
 
-inventory::submit!(Aoc::new(2025, 10, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 10, parse, part1, part2,));
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_unreachable_lights() {
+        let machines = parse(&mut "[.#] (1) {1,2}\n[#.] (1) {1,0}\n".as_bytes()).unwrap();
+        let error = part1(&machines).unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert!(matches!(error.kind, crate::ErrorKind::NoSolution(_)));
+    }
     #[test]
     fn test_find_least_buttons_for_machine_joltage_ex1() {
         // [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
@@ -186,7 +189,10 @@ mod tests {
             joltage_requirement: vec![3, 4, 3, 7],
         };
 
-        assert_eq!(find_least_buttons_for_machine_joltage(&machine), 10);
+        assert_eq!(
+            find_least_buttons_for_machine_joltage(&machine).unwrap(),
+            10
+        );
     }
 
     #[test]
@@ -204,7 +210,10 @@ mod tests {
             joltage_requirement: vec![7, 5, 12, 7, 2],
         };
 
-        assert_eq!(find_least_buttons_for_machine_joltage(&machine), 12);
+        assert_eq!(
+            find_least_buttons_for_machine_joltage(&machine).unwrap(),
+            12
+        );
     }
 
     #[test]
@@ -221,7 +230,10 @@ mod tests {
             joltage_requirement: vec![10, 11, 11, 5, 10, 5],
         };
 
-        assert_eq!(find_least_buttons_for_machine_joltage(&machine), 11);
+        assert_eq!(
+            find_least_buttons_for_machine_joltage(&machine).unwrap(),
+            11
+        );
     }
 
     #[test]
@@ -254,6 +266,9 @@ mod tests {
         // 18, 0,2,4
         // 19, 2,3,5
 
-        assert_eq!(find_least_buttons_for_machine_joltage(&machine), 73);
+        assert_eq!(
+            find_least_buttons_for_machine_joltage(&machine).unwrap(),
+            73
+        );
     }
 }
@@ -3,22 +3,25 @@ use std::{
     io::{BufRead, BufReader, Read},
 };
 
-use crate::Aoc;
+use crate::{Answer, Error, Result};
 
-fn parse(buf: &mut dyn Read) -> HashMap<String, HashSet<String>> {
+fn parse(buf: &mut dyn Read) -> Result<HashMap<String, HashSet<String>>> {
     let buf_reader = BufReader::new(buf);
 
     buf_reader
         .lines()
-        .map(|line| {
-            let line = line.unwrap();
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line?;
 
-            let (from_label, to_labels) = line.split_once(':').unwrap();
+            let (from_label, to_labels) = line
+                .split_once(':')
+                .ok_or_else(|| Error::parse("expected '<device>: <outputs>'").at_line(i + 1))?;
             let to_labels: HashSet<String> = to_labels
                 .split(' ')
                 .map(|label| label.trim().to_string())
                 .collect();
-            (from_label.to_string(), to_labels)
+            Ok((from_label.to_string(), to_labels))
         })
         .collect()
 }
@@ -47,24 +50,18 @@ fn count_routes_between(edges: &HashMap<String, HashSet<String>>, a: &str, b: &s
     route_counts.get(b).copied().unwrap_or_default()
 }
 
-fn part1(buf: &mut dyn Read) {
-    let edges = parse(buf);
-    let answer = count_routes_between(&edges, "you", "out");
+fn part1(edges: &HashMap<String, HashSet<String>>) -> Result<Answer> {
+    let answer = count_routes_between(edges, "you", "out");
 
-    println!("Part 1: {}", answer);
+    Ok(answer.into())
 }
 
-fn part2(buf: &mut dyn Read) {
-    let edges = parse(buf);
+fn part2(edges: &HashMap<String, HashSet<String>>) -> Result<Answer> {
+    let svr_fft_routes = count_routes_between(edges, "svr", "fft");
+    let fft_dac_routes = count_routes_between(edges, "fft", "dac");
+    let dac_out_routes = count_routes_between(edges, "dac", "out");
 
-    let svr_fft_routes = count_routes_between(&edges, "svr", "fft");
-    let fft_dac_routes = count_routes_between(&edges, "fft", "dac");
-    let dac_out_routes = count_routes_between(&edges, "dac", "out");
-
-    println!(
-        "Part 2: {}",
-        svr_fft_routes * fft_dac_routes * dac_out_routes
-    );
+    Ok((svr_fft_routes * fft_dac_routes * dac_out_routes).into())
 }
 
-inventory::submit!(Aoc::new(2025, 11, part1, part2,));
+inventory::submit!(parsed_aoc!(2025, 11, parse, part1, part2,));
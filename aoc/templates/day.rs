@@ -0,0 +1,30 @@
+use std::io::Read;
+
+use crate::{Answer, Error, Result};
+
+fn parse(buf: &mut dyn Read) -> Result<String> {
+    let mut input = String::new();
+    buf.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+fn part1(_input: &str) -> Result<Answer> {
+    Err(Error::no_solution("not solved yet"))
+}
+
+fn part2(_input: &str) -> Result<Answer> {
+    Err(Error::no_solution("not solved yet"))
+}
+
+inventory::submit!(parsed_aoc!(__YEAR__, __DAY__, parse, part1, part2,));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = parse(&mut &b""[..]).unwrap();
+        assert_eq!(input, "");
+    }
+}
@@ -0,0 +1,26 @@
+//! Runs each solution against the examples in `examples/`, with one test per
+//! example generated by `build.rs`.
+
+use std::path::Path;
+
+use advent_of_code::{
+    examples::{examples_dir, Example, EXAMPLES_DIR},
+    selection::Selection,
+};
+
+fn check(year: usize, day: usize, name: &str) {
+    let selection = Selection {
+        year: Some(year),
+        days: Some(day..=day),
+    };
+    let Some(solution) = selection.solutions().into_iter().next() else {
+        panic!("there is no solution for {}", selection);
+    };
+
+    let path = examples_dir(Path::new(EXAMPLES_DIR), year, day).join(format!("{}.txt", name));
+    let example = Example::load(&path).unwrap_or_else(|error| panic!("{}", error));
+    let failures = example.check(solution);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
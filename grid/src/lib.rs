@@ -103,7 +103,7 @@ impl Location {
 
     pub fn area(&self, other: &Location) -> usize {
         let diagonal = self.abs_diff(other);
-        return diagonal.x * diagonal.y;
+        diagonal.x * diagonal.y
     }
 }
 